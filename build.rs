@@ -0,0 +1,63 @@
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// For every examples/<year>/day<day>/ directory, writes
+// $OUT_DIR/examples/<year>/day<day>.rs with a test per `.txt` fixture in it,
+// which `example_tests!` includes into the day.
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=examples");
+
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("examples");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples");
+    if !root.is_dir() {
+        return Ok(());
+    }
+
+    for year in fs::read_dir(&root)? {
+        let year = year?.path();
+        if !year.is_dir() {
+            continue;
+        }
+        let year_out = out.join(year.file_name().unwrap());
+        fs::create_dir_all(&year_out)?;
+
+        for day in fs::read_dir(&year)? {
+            let day = day?.path();
+            if !day.is_dir() {
+                continue;
+            }
+
+            let mut fixtures = fs::read_dir(&day)?
+                .map(|f| f.map(|f| f.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            fixtures.retain(|f| f.extension().is_some_and(|ext| ext == "txt"));
+            fixtures.sort();
+
+            let mut tests = String::new();
+            for fixture in fixtures {
+                let stem = fixture.file_stem().unwrap().to_string_lossy();
+                let name = stem
+                    .chars()
+                    .map(|c| match c.is_ascii_alphanumeric() {
+                        true => c.to_ascii_lowercase(),
+                        false => '_',
+                    })
+                    .collect::<String>();
+                tests.push_str(&format!(
+                    "#[test]\nfn example_{}() {{\n    check({:?});\n}}\n\n",
+                    name,
+                    fixture.display().to_string()
+                ));
+            }
+
+            let file = format!("{}.rs", day.file_name().unwrap().to_string_lossy());
+            fs::write(year_out.join(file), tests)?;
+        }
+    }
+    return Ok(());
+}
@@ -1,21 +1,33 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-    let pt1: i64 = lines.iter().map(|line| parse_line_pt1(line)).sum();
-    println!("Part 1: {}", pt1);
-    let pt2: i64 = lines.iter().map(|line| parse_line_pt2(line)).sum();
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> i64 {
+        lines.iter().map(|line| parse_line_pt1(line)).sum()
+    }
+
+    fn part2(lines: &Self::Input) -> i64 {
+        lines.iter().map(|line| parse_line_pt2(line)).sum()
+    }
 }
 
 fn parse_line_pt1(s: &str) -> i64 {
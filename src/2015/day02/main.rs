@@ -1,62 +1,42 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-    let pt1: i64 = lines
-        .iter()
-        .map(|s| parse_line(s))
-        .map(|x| compute_pt1(x))
-        .sum();
-    println!("Part 1: {}", pt1);
-    let pt2: i64 = lines
-        .iter()
-        .map(|s| parse_line(s))
-        .map(|x| compute_pt2(x))
-        .sum();
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
 }
 
-fn parse_line(s: &str) -> (i64, i64, i64) {
-    let mut x = 0;
-    let mut y = 0;
-    let mut z = 0;
-    let dim_re = Regex::new(r"(\d+)x(\d+)x(\d+)").unwrap();
-    for m in dim_re.captures_iter(s) {
-        for (i, capt) in m.iter().enumerate() {
-            if i == 0 {
-                continue;
-            }
-
-            if let Some(sub) = capt {
-                let val = sub.as_str().parse::<i64>().unwrap();
-                match i {
-                    1 => {
-                        x = val;
-                    }
-                    2 => {
-                        y = val;
-                    }
-                    3 => {
-                        z = val;
-                    }
-                    _ => {}
-                }
-            }
-        }
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 2;
+
+    type Input = Vec<(i64, i64, i64)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(i + 1, line))
+            .collect()
     }
 
-    return (x, y, z);
+    fn part1(boxes: &Self::Input) -> i64 {
+        boxes.iter().map(|x| compute_pt1(*x)).sum()
+    }
+
+    fn part2(boxes: &Self::Input) -> i64 {
+        boxes.iter().map(|x| compute_pt2(*x)).sum()
+    }
+}
+
+fn parse_line(line_no: usize, s: &str) -> Result<(i64, i64, i64), AocError> {
+    scan!(line_no, s, "{}x{}x{}" => i64, i64, i64)
 }
 
 fn compute_pt1((x, y, z): (i64, i64, i64)) -> i64 {
@@ -80,7 +60,12 @@ mod tests {
         let tests = [("2x3x4", 58), ("1x1x10", 43)];
 
         for (input, want) in tests {
-            assert_eq!(compute_pt1(parse_line(input)), want, "for input {}", input);
+            assert_eq!(
+                compute_pt1(parse_line(1, input).unwrap()),
+                want,
+                "for input {}",
+                input
+            );
         }
     }
 
@@ -89,7 +74,12 @@ mod tests {
         let tests = [("2x3x4", 34), ("1x1x10", 14)];
 
         for (input, want) in tests {
-            assert_eq!(compute_pt2(parse_line(input)), want, "for input {}", input);
+            assert_eq!(
+                compute_pt2(parse_line(1, input).unwrap()),
+                want,
+                "for input {}",
+                input
+            );
         }
     }
 }
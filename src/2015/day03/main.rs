@@ -1,23 +1,35 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
 use std::collections::HashSet;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-    let pt1: usize = lines.iter().map(|x| handle_pt1(x)).sum();
-    println!("Part 1: {}", pt1);
-    let pt2: usize = lines.iter().map(|x| handle_pt2(x)).sum();
-    println!("Part 2: {}", pt2);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        lines.iter().map(|x| handle_pt1(x)).sum()
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        lines.iter().map(|x| handle_pt2(x)).sum()
+    }
 }
 
 fn handle_pt1(s: &str) -> usize {
@@ -25,7 +37,7 @@ fn handle_pt1(s: &str) -> usize {
 
     let mut curr: Point<i32> = Point::<i32> { x: 0, y: 0 };
     set.insert(curr);
-    for c in s.chars().into_iter() {
+    for c in s.chars() {
         curr = next(curr, c);
         set.insert(curr);
     }
@@ -38,7 +50,7 @@ fn handle_pt2(s: &str) -> usize {
     let mut santa: Point<i32> = Point::<i32> { x: 0, y: 0 };
     let mut robo: Point<i32> = Point::<i32> { x: 0, y: 0 };
     set.insert(santa);
-    for (i, c) in s.chars().enumerate().into_iter() {
+    for (i, c) in s.chars().enumerate() {
         if i % 2 == 0 {
             santa = next(santa, c);
             set.insert(santa);
@@ -1,9 +1,35 @@
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+
 fn main() {
-    let secret = "ckczppom";
-    let pt1: usize = handle_pt1(secret);
-    println!("Part 1: {}", pt1);
-    let pt2: usize = handle_pt2(secret);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 4;
+
+    /// The secret key, e.g. "ckczppom".
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        match lines.first() {
+            Some(secret) => Ok(secret.trim().to_owned()),
+            None => Err(AocError::parse(1, "missing secret key")),
+        }
+    }
+
+    fn part1(secret: &Self::Input) -> usize {
+        handle_pt1(secret)
+    }
+
+    fn part2(secret: &Self::Input) -> usize {
+        handle_pt2(secret)
+    }
 }
 
 fn handle_pt1(s: &str) -> usize {
@@ -1,34 +1,34 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-    let pt1: usize = lines
-        .iter()
-        .map(|x| match is_nice_pt1(x) {
-            true => 1,
-            false => 0,
-        })
-        .sum();
-    println!("Part 1: {}", pt1);
-    let pt2: usize = lines
-        .iter()
-        .map(|x| match is_nice_pt2(x) {
-            true => 1,
-            false => 0,
-        })
-        .sum();
-    println!("Part 2: {}", pt2);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 5;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        lines.iter().filter(|x| is_nice_pt1(x)).count()
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        lines.iter().filter(|x| is_nice_pt2(x)).count()
+    }
 }
 
 /*
@@ -1,73 +1,85 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
-use regex::Regex;
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
-
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-    let instructions: Vec<(&str, Point<usize>, Point<usize>)> =
-        lines.iter().map(|x| parse_line(x)).collect();
-    let pt1 = handle_pt1(&instructions);
-    println!("Part 1: {}", pt1);
-    let pt2 = handle_pt2(&instructions);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
 }
 
-fn parse_line(s: &str) -> (&str, Point<usize>, Point<usize>) {
-    let instr_re =
-        Regex::new(r"(turn on|toggle|turn off) (\d+),(\d+) through (\d+),(\d+)").unwrap();
-    let mut action: &str = "";
-    let mut start: Point<usize> = Point::<usize> { x: 0, y: 0 };
-    let mut end: Point<usize> = Point::<usize> { x: 0, y: 0 };
-    for m in instr_re.captures_iter(s) {
-        for (i, capt) in m.iter().enumerate() {
-            if let Some(sub) = capt {
-                match i {
-                    1 => {
-                        action = sub.as_str();
-                    }
-                    2 => {
-                        start.x = sub.as_str().parse::<usize>().unwrap();
-                    }
-                    3 => {
-                        start.y = sub.as_str().parse::<usize>().unwrap();
-                    }
-                    4 => {
-                        end.x = sub.as_str().parse::<usize>().unwrap();
-                    }
-                    5 => {
-                        end.y = sub.as_str().parse::<usize>().unwrap();
-                    }
-                    _ => {}
-                }
-            }
-        }
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 6;
+
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(i + 1, line))
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input) -> i32 {
+        handle_pt1(instructions)
     }
 
-    return (action, start, end);
+    fn part2(instructions: &Self::Input) -> i32 {
+        handle_pt2(instructions)
+    }
 }
 
-fn handle_pt1(instructions: &Vec<(&str, Point<usize>, Point<usize>)>) -> i32 {
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Action {
+    TurnOn,
+    Toggle,
+    TurnOff,
+}
+
+type Instruction = (Action, Point<usize>, Point<usize>);
+
+fn parse_line(line_no: usize, s: &str) -> Result<Instruction, AocError> {
+    let actions = [
+        ("turn on ", Action::TurnOn),
+        ("toggle ", Action::Toggle),
+        ("turn off ", Action::TurnOff),
+    ];
+    let Some((action, corners)) = actions
+        .into_iter()
+        .find_map(|(prefix, action)| Some((action, s.strip_prefix(prefix)?)))
+    else {
+        return Err(AocError::parse(
+            line_no,
+            format!("unknown instruction {:?}", s),
+        ));
+    };
+    let (x1, y1, x2, y2) =
+        scan!(line_no, corners, "{},{} through {},{}" => usize, usize, usize, usize)?;
+    return Ok((action, Point::new(x1, y1), Point::new(x2, y2)));
+}
+
+fn handle_pt1(instructions: &Vec<Instruction>) -> i32 {
     let mut grid = [[false; 1000]; 1000];
 
     for (action, start, end) in instructions {
         for x in start.x..=end.x {
             for y in start.y..=end.y {
-                grid[x][y] = match *action {
-                    "turn on" => true,
-                    "toggle" => !grid[x][y],
-                    "turn off" => false,
-                    _ => false,
+                grid[x][y] = match action {
+                    Action::TurnOn => true,
+                    Action::Toggle => !grid[x][y],
+                    Action::TurnOff => false,
                 };
             }
         }
@@ -78,7 +90,7 @@ fn handle_pt1(instructions: &Vec<(&str, Point<usize>, Point<usize>)>) -> i32 {
         .sum::<i32>()
 }
 
-fn handle_pt2(instructions: &Vec<(&str, Point<usize>, Point<usize>)>) -> i32 {
+fn handle_pt2(instructions: &Vec<Instruction>) -> i32 {
     let mut grid: HashMap<Point<usize>, i32> = HashMap::new();
 
     for (action, start, end) in instructions {
@@ -90,11 +102,10 @@ fn handle_pt2(instructions: &Vec<(&str, Point<usize>, Point<usize>)>) -> i32 {
                     brightness = *b;
                 }
 
-                brightness += match *action {
-                    "turn on" => 1,
-                    "toggle" => 2,
-                    "turn off" => -1,
-                    _ => 0,
+                brightness += match action {
+                    Action::TurnOn => 1,
+                    Action::Toggle => 2,
+                    Action::TurnOff => -1,
                 };
                 if brightness < 0 {
                     brightness = 0;
@@ -129,8 +140,10 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            let instructions: Vec<(&str, Point<usize>, Point<usize>)> =
-                input.iter().map(|x| parse_line(x)).collect();
+            let instructions = input
+                .iter()
+                .map(|x| parse_line(1, x).unwrap())
+                .collect::<Vec<Instruction>>();
             assert_eq!(
                 handle_pt1(&instructions),
                 want,
@@ -150,8 +163,10 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            let instructions: Vec<(&str, Point<usize>, Point<usize>)> =
-                input.iter().map(|x| parse_line(x)).collect();
+            let instructions = input
+                .iter()
+                .map(|x| parse_line(1, x).unwrap())
+                .collect::<Vec<Instruction>>();
             assert_eq!(
                 handle_pt2(&instructions),
                 want,
@@ -160,4 +175,14 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line(1, "turn off 499,499 through 500,500").unwrap(),
+            (Action::TurnOff, Point::new(499, 499), Point::new(500, 500))
+        );
+        assert!(parse_line(1, "switch 0,0 through 1,1").is_err());
+        assert!(parse_line(1, "toggle 0,0 to 1,1").is_err());
+    }
 }
@@ -0,0 +1,74 @@
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::math;
+use regex::Regex;
+
+fn main() {
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 25;
+
+    /// The row and column of the code the machine wants, counting from 1.
+    type Input = (u64, u64);
+    type Part1 = i64;
+    type Part2 = Unsolved;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        let re = Regex::new(r"row (\d+), column (\d+)").unwrap();
+        let Some(caps) = lines.first().and_then(|line| re.captures(line)) else {
+            return Err(AocError::parse(1, "expected a row and a column"));
+        };
+        Ok((parse_at(1, &caps[1])?, parse_at(1, &caps[2])?))
+    }
+
+    fn part1(&(row, column): &Self::Input) -> i64 {
+        code_at(row, column)
+    }
+
+    fn part2(_: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+}
+
+const FIRST_CODE: i64 = 20151125;
+const MULTIPLIER: i64 = 252533;
+const MODULUS: i64 = 33554393;
+
+/// The codes are filled in along the diagonals going up and to the right,
+/// each one the last times `MULTIPLIER`, so the code at a spot is the first
+/// times `MULTIPLIER` to the power of how many came before it.
+fn code_at(row: u64, column: u64) -> i64 {
+    let diagonal = row + column - 1;
+    let before = diagonal * (diagonal - 1) / 2 + column - 1;
+    return FIRST_CODE * math::mod_pow(MULTIPLIER, before, MODULUS) % MODULUS;
+}
+
+advent_of_code::example_tests!(Day, "2015/day25");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_at() {
+        let tests = [
+            ((1, 1), 20151125),
+            ((2, 1), 31916031),
+            ((1, 2), 18749137),
+            ((4, 3), 21345942),
+            ((6, 6), 27995004),
+        ];
+
+        for ((row, column), want) in tests {
+            assert_eq!(code_at(row, column), want, "row {}, column {}", row, column);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// Each day is also built as its own binary, which is the only place its
+// `main` is used.
+#![allow(dead_code)]
+
+use crate::registry::Entry;
+
+#[path = "day01/main.rs"]
+pub mod day01;
+
+#[path = "day02/main.rs"]
+pub mod day02;
+
+#[path = "day03/main.rs"]
+pub mod day03;
+
+#[path = "day04/main.rs"]
+pub mod day04;
+
+#[path = "day05/main.rs"]
+pub mod day05;
+
+#[path = "day06/main.rs"]
+pub mod day06;
+
+#[path = "day25/main.rs"]
+pub mod day25;
+
+pub fn days() -> Vec<Entry> {
+    vec![
+        Entry::of::<day01::Day>(),
+        Entry::of::<day02::Day>(),
+        Entry::of::<day03::Day>(),
+        Entry::of::<day04::Day>(),
+        Entry::of::<day05::Day>(),
+        Entry::of::<day06::Day>(),
+        Entry::of::<day25::Day>(),
+    ]
+}
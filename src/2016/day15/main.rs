@@ -0,0 +1,69 @@
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::math;
+
+fn main() {
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 15;
+
+    type Input = Vec<Disc>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let (_, positions, start) = scan!(
+                    i + 1,
+                    line,
+                    "Disc #{} has {} positions; at time=0, it is at position {}." => usize, i64, i64
+                )?;
+                Ok(Disc { positions, start })
+            })
+            .collect()
+    }
+
+    fn part1(discs: &Self::Input) -> i64 {
+        first_press(discs)
+    }
+
+    fn part2(discs: &Self::Input) -> i64 {
+        let mut discs = discs.clone();
+        discs.push(Disc {
+            positions: 11,
+            start: 0,
+        });
+        first_press(&discs)
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Disc {
+    positions: i64,
+    start: i64,
+}
+
+/// The capsule gets to the disc at depth `i` (counting from 1) `i` seconds
+/// after the button is pressed, and falls through if the disc has spun round
+/// to position 0 by then.
+fn first_press(discs: &Vec<Disc>) -> i64 {
+    let congruences = discs
+        .iter()
+        .zip(1..)
+        .map(|(disc, depth)| (-(disc.start + depth), disc.positions));
+    let (time, _) = math::crt(congruences).expect("the slots never line up");
+    return time;
+}
+
+advent_of_code::example_tests!(Day, "2016/day15");
@@ -0,0 +1,12 @@
+// Each day is also built as its own binary, which is the only place its
+// `main` is used.
+#![allow(dead_code)]
+
+use crate::registry::Entry;
+
+#[path = "day15/main.rs"]
+pub mod day15;
+
+pub fn days() -> Vec<Entry> {
+    vec![Entry::of::<day15::Day>()]
+}
@@ -0,0 +1,76 @@
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::math;
+
+fn main() {
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 13;
+
+    /// The earliest departure, and each bus ID with its offset in the list.
+    type Input = (i64, Vec<(i64, i64)>);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        let (Some(earliest), Some(buses)) = (lines.first(), lines.get(1)) else {
+            return Err(AocError::parse(
+                lines.len() + 1,
+                "expected a timestamp and a list of buses",
+            ));
+        };
+
+        let buses = buses
+            .split(',')
+            .zip(0..)
+            .filter(|(id, _)| *id != "x")
+            .map(|(id, offset)| Ok((parse_at(2, id)?, offset)))
+            .collect::<Result<Vec<(i64, i64)>, AocError>>()?;
+        Ok((parse_at(1, earliest)?, buses))
+    }
+
+    fn part1((earliest, buses): &Self::Input) -> i64 {
+        let (wait, id) = buses
+            .iter()
+            .map(|&(id, _)| (math::modulo(-earliest, id), id))
+            .min()
+            .unwrap();
+        wait * id
+    }
+
+    fn part2((_, buses): &Self::Input) -> i64 {
+        // Bus `id` leaves `offset` minutes after t when t + offset = 0 mod id.
+        let (t, _) = math::crt(buses.iter().map(|&(id, offset)| (-offset, id)))
+            .expect("the buses never line up");
+        t
+    }
+}
+
+advent_of_code::example_tests!(Day, "2020/day13");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part2() {
+        let tests = [
+            ("17,x,13,19", 3417),
+            ("67,7,59,61", 754018),
+            ("67,x,7,59,61", 779210),
+            ("67,7,x,59,61", 1261476),
+            ("1789,37,47,1889", 1202161486),
+        ];
+
+        for (buses, want) in tests {
+            let input = Day::parse(&[String::from("0"), String::from(buses)]).unwrap();
+            assert_eq!(Day::part2(&input), want, "for buses {}", buses);
+        }
+    }
+}
@@ -1,17 +1,37 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::len_zero, clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+
+    /// Calories carried by the three most loaded elves.
+    type Input = [usize; 3];
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(top_three(lines))
+    }
+
+    fn part1(max: &Self::Input) -> usize {
+        *max.iter().max().unwrap()
+    }
+
+    fn part2(max: &Self::Input) -> usize {
+        max.iter().sum::<usize>()
+    }
+}
+
+fn top_three(lines: &[String]) -> [usize; 3] {
     let mut max: [usize; 3] = [0, 0, 0];
     let mut elf = 0;
     for line in lines {
@@ -28,6 +48,5 @@ fn main() {
         }
         elf += line.parse::<usize>().unwrap();
     }
-    println!("Part 1: {}", max.iter().max().unwrap());
-    println!("Part 2: {}", max.iter().sum::<usize>());
+    return max;
 }
@@ -1,22 +1,38 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(_lines: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+
+    fn part2(lines: &Self::Input) -> i32 {
+        handle_pt2(lines)
+    }
+}
 
+fn handle_pt2(lines: &Vec<String>) -> i32 {
     let mut score = 0;
 
-    // File must exist in current path before this produces output
-    // Consumes the iterator, returns an (Optional) String
     for line in lines {
         let parts = line.split(" ").collect::<Vec<&str>>();
         assert!(parts.len() == 2);
@@ -28,7 +44,7 @@ fn main() {
 
         score += outcome + choice_points;
     }
-    println!("Total points: {}", score);
+    return score;
 }
 
 fn decode_play(code: &str) -> &str {
@@ -1,25 +1,42 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
 use std::collections::HashSet;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(_lines: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+
+    fn part2(lines: &Self::Input) -> i32 {
+        handle_pt2(lines)
+    }
+}
 
+fn handle_pt2(lines: &Vec<String>) -> i32 {
     let mut priority = 0;
     let mut left_freqs: HashSet<char> = HashSet::new();
     let mut middle_freqs: HashSet<char> = HashSet::new();
     let mut right_freqs: HashSet<char> = HashSet::new();
 
-    // File must exist in current path before this produces output
     let mut i = 0;
     for line in lines {
         for c in line.chars() {
@@ -50,7 +67,7 @@ fn main() {
             right_freqs.clear();
         }
     }
-    println!("Total priority: {}", priority);
+    return priority;
 }
 
 fn to_priority(c: char) -> i32 {
@@ -1,47 +1,66 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::{Solution, Unsolved};
+use advent_of_code::utils::interval::Inclusive;
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
 
-    let mut count = 0;
+pub struct Day;
 
-    // Consumes the iterator, returns an (Optional) String
-    for line in lines {
-        if parse_line(line) {
-            count += 1
-        }
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Inclusive<i32>, Inclusive<i32>)>;
+    type Part1 = Unsolved;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(i + 1, line))
+            .collect()
     }
-    println!("Total overlapping: {}", count);
-}
 
-fn range_overlaps(start1: i32, end1: i32, start2: i32, end2: i32) -> bool {
-    // not overlapping
-    return !(end1 < start2 || end2 < start1);
-}
+    fn part1(_pairs: &Self::Input) -> Unsolved {
+        Unsolved
+    }
 
-fn parse_range(rng: &str) -> (i32, i32) {
-    let parts = rng.split("-").collect::<Vec<&str>>();
-    return (
-        parts[0].parse::<i32>().unwrap(),
-        parts[1].parse::<i32>().unwrap(),
-    );
+    fn part2(pairs: &Self::Input) -> usize {
+        pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
+    }
 }
 
-fn parse_line(line: &String) -> bool {
-    let parts = line.split(",").collect::<Vec<&str>>();
+/// The two elves' sections, e.g. "2-4,6-8".
+fn parse_line(line_no: usize, line: &str) -> Result<(Inclusive<i32>, Inclusive<i32>), AocError> {
+    let (a_first, a_last, b_first, b_last) =
+        scan!(line_no, line, "{}-{},{}-{}" => i32, i32, i32, i32)?;
+    return Ok((
+        Inclusive::new(a_first, a_last),
+        Inclusive::new(b_first, b_last),
+    ));
+}
 
-    let (r1_start, r1_end) = parse_range(parts[0]);
-    let (r2_start, r2_end) = parse_range(parts[1]);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    return range_overlaps(r1_start, r1_end, r2_start, r2_end);
+    #[test]
+    fn test_parse_errors() {
+        let tests = [(vec!["2-4,6-8", "2-4"], 2), (vec!["2-x,6-8"], 1)];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Day::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
+        }
+    }
 }
+
+advent_of_code::example_tests!(Day, "2022/day04");
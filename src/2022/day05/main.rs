@@ -1,45 +1,104 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::{Solution, Unsolved};
+use advent_of_code::utils::parse::numbered_sections;
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
 use regex::Regex;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 5;
+
+    type Input = Procedure;
+    type Part1 = Unsolved;
+    type Part2 = String;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Procedure::parse(lines)
+    }
+
+    fn part1(_procedure: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+
+    fn part2(procedure: &Self::Input) -> String {
+        handle_pt2(procedure)
+    }
+
+    fn visualize(procedure: &Self::Input, viz: &mut Visualizer) {
+        crane(procedure, |ship| viz.frame(&ship.draw()));
+    }
+}
+
+/// The drawing of the starting stacks, and how many crates to move from which
+/// stack to which (counting stacks from 1).
+#[derive(Debug, Clone)]
+pub struct Procedure {
+    drawing: Vec<String>,
+    moves: Vec<(i32, i32, i32)>,
+}
 
-    let mut past_header = false;
+impl Procedure {
+    pub fn parse(lines: &[String]) -> Result<Self, AocError> {
+        let [(_, drawing), (start, moves)] = numbered_sections(lines)[..] else {
+            return Err(AocError::parse(
+                1,
+                "expected the stacks, a blank line and then the moves",
+            ));
+        };
+        let moves = moves
+            .iter()
+            .zip(start..)
+            .map(|(line, line_no)| scan!(line_no, line, "move {} from {} to {}" => i32, i32, i32))
+            .collect::<Result<Vec<(i32, i32, i32)>, AocError>>()?;
+        return Ok(Self {
+            drawing: drawing.to_vec(),
+            moves,
+        });
+    }
+}
+
+fn handle_pt2(procedure: &Procedure) -> String {
+    return crane(procedure, |_| {}).peek_top();
+}
+
+/// Loads the ship and moves the crates around, calling `on_move` with the ship
+/// before the first move and after every one.
+fn crane<F: FnMut(&Ship)>(procedure: &Procedure, mut on_move: F) -> Ship {
     let container_re = Regex::new(r"(\[\w\]\s*)+").unwrap();
     let mut ship = Ship::with_buckets(9);
 
-    // File must exist in current path before this produces output
-    // Consumes the iterator, returns an (Optional) String
-    for line in lines {
-        if !past_header && container_re.is_match(line.as_str()) {
-            for (i, c) in line.chars().enumerate() {
-                match c {
-                    '[' | ']' | ' ' => (),
-                    _ => {
-                        let idx = (i - 1) / 4;
-                        ship.push_container(idx as i32, c);
-                    }
+    // The last line of the drawing numbers the stacks, so has no crates.
+    for line in &procedure.drawing {
+        if !container_re.is_match(line.as_str()) {
+            continue;
+        }
+        for (i, c) in line.chars().enumerate() {
+            match c {
+                '[' | ']' | ' ' => (),
+                _ => {
+                    let idx = (i - 1) / 4;
+                    ship.push_container(idx as i32, c);
                 }
             }
-        } else if !past_header {
-            past_header = true;
-            ship.finalize_initial_state();
-        } else {
-            let (num_to_move, src, dst) = parse_move(line.as_str());
-            ship.move_container(num_to_move, src - 1, dst - 1);
         }
     }
-    println!("Total overlapping: {}", ship.peek_top());
+    ship.finalize_initial_state();
+    on_move(&ship);
+
+    for &(num_to_move, src, dst) in &procedure.moves {
+        ship.move_container(num_to_move, src - 1, dst - 1);
+        on_move(&ship);
+    }
+    return ship;
 }
 
 pub struct Ship {
@@ -89,10 +148,29 @@ impl Ship {
         return top.to_string();
     }
 
-    pub fn print(&mut self) {
-        for i in 0..self.stacks.len() {
-            println!("{}: {:?}", i, self.stacks[i]);
+    /// The stacks drawn the same way as the input.
+    pub fn draw(&self) -> Frame {
+        let height = self.stacks.iter().map(|stk| stk.size()).max().unwrap_or(0);
+        let mut rows = Vec::new();
+        for level in (0..height).rev() {
+            let mut row = Vec::new();
+            for stk in self.stacks.iter() {
+                match stk.items.get(level) {
+                    Some(&c) => row.extend(['[', c, ']', ' ']),
+                    None => row.extend([' '; 4]),
+                }
+            }
+            rows.push(row);
         }
+        rows.push(
+            (1..=self.stacks.len())
+                .flat_map(|i| format!(" {}  ", i).chars().collect::<Vec<char>>())
+                .collect(),
+        );
+        return viz::from_rows(&rows, |&c| match c {
+            'A'..='Z' => Cell::colored(c, Rgb(220, 140, 60)),
+            _ => Cell::from(c),
+        });
     }
 }
 
@@ -131,24 +209,4 @@ impl<T> Stack<T> {
     }
 }
 
-fn parse_move(line: &str) -> (i32, i32, i32) {
-    let mut num_to_move = 0;
-    let mut src = 0;
-    let mut dst = 0;
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)$").unwrap();
-    for m in re.captures_iter(line) {
-        for (i, capt) in m.iter().enumerate() {
-            if capt.is_some() {
-                if let Some(thing) = capt {
-                    match i {
-                        1 => num_to_move = thing.as_str().parse::<i32>().unwrap(),
-                        2 => src = thing.as_str().parse::<i32>().unwrap(),
-                        3 => dst = thing.as_str().parse::<i32>().unwrap(),
-                        _ => (),
-                    }
-                }
-            }
-        }
-    }
-    return (num_to_move, src, dst);
-}
+advent_of_code::example_tests!(Day, "2022/day05");
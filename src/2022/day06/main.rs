@@ -1,22 +1,40 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 6;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
 
+    fn part1(lines: &Self::Input) -> usize {
+        find_marker(lines, 4)
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        find_marker(lines, 14)
+    }
+}
+
+/// Number of characters parsed before the last `size` were all different.
+fn find_marker(lines: &Vec<String>, size: usize) -> usize {
     let mut uniques: HashMap<char, i32> = HashMap::new();
-    let size = 14;
-    let mut circular: Vec<char> = Vec::with_capacity(size as usize);
+    let mut circular: Vec<char> = Vec::with_capacity(size);
     for _i in 0..size {
         circular.push(0 as char);
     }
@@ -38,14 +56,12 @@ fn main() {
             circular[next_idx] = c;
             next_idx = (next_idx + 1) % size;
             let count = uniques.entry(c).or_insert(0);
-            *count = *count + 1;
+            *count += 1;
 
-            if i >= size {
-                if uniques.len() == size {
-                    println!("Parsed {} characters", i + 1);
-                    return;
-                }
+            if i >= size && uniques.len() == size {
+                return i + 1;
             }
         }
     }
+    return 0;
 }
@@ -1,32 +1,54 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(
+    clippy::needless_return,
+    clippy::ptr_arg,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 7;
+
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(_lines: &Self::Input) -> Unsolved {
+        Unsolved
+    }
 
+    fn part2(lines: &Self::Input) -> i32 {
+        let fs = build_fs(lines);
+        let to_delete = NEED_FREE - (TOTAL_SIZE - fs.total_size());
+        fs.get_smallest_greater_than(to_delete)
+    }
+}
+
+fn build_fs(lines: &Vec<String>) -> FileSystem {
     let mut fs = FileSystem::empty();
     let mut curr_dir = "/".to_owned();
     fs.add_dir(&curr_dir);
 
     let mut is_ls_output = false;
 
-    // File must exist in current path before this produces output
-    // Consumes the iterator, returns an (Optional) String
     for line in lines {
-        if let Some(dirname) = is_cd(&line) {
+        if let Some(dirname) = is_cd(line) {
             if dirname == "/" {
                 continue;
             } else if dirname == ".." {
@@ -37,10 +59,10 @@ fn main() {
                 curr_dir = path_join(&curr_dir, dirname);
                 fs.add_dir(&curr_dir);
             }
-        } else if is_ls(&line) {
+        } else if is_ls(line) {
             is_ls_output = true;
         } else if is_ls_output {
-            let (name, maybe_size) = parse_ls_line(&line);
+            let (name, maybe_size) = parse_ls_line(line);
             let new_name = path_join(&curr_dir, name);
             if let Some(size) = maybe_size {
                 fs.add_file(&new_name, size);
@@ -52,12 +74,7 @@ fn main() {
         }
     }
     fs.calculate_sizes();
-    let to_delete = NEED_FREE - (TOTAL_SIZE - fs.total_size());
-    println!(
-        "FS just over {}: {}",
-        to_delete,
-        fs.get_smallest_greater_than(to_delete)
-    )
+    return fs;
 }
 
 static TOTAL_SIZE: i32 = 70000000;
@@ -105,7 +122,7 @@ impl FileSystem {
         }
     }
 
-    fn update_parent<'a, 'b>(&mut self, name: &String, obj: RefCell<FSObject>) {
+    fn update_parent(&mut self, name: &String, obj: RefCell<FSObject>) {
         self.details.entry(name.clone().to_owned()).or_insert(obj);
         if let Some(parent) = get_parent(name.as_str()) {
             let parent_entry = self
@@ -163,10 +180,8 @@ impl FileSystem {
         let mut min = TOTAL_SIZE;
         for obj in self.details.values() {
             let obj = obj.borrow();
-            if obj.is_dir && obj.size >= over_size {
-                if obj.size < min {
-                    min = obj.size;
-                }
+            if obj.is_dir && obj.size >= over_size && obj.size < min {
+                min = obj.size;
             }
         }
         return min;
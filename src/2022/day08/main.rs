@@ -1,56 +1,56 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_range_loop, clippy::needless_return, clippy::ptr_arg)]
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 8;
 
-    // Initialize the matrix.
-    let num_rows = lines.len();
-    let mut num_columns = 0;
-    for line in lines.iter() {
-        assert!(line.len() > 0);
-        num_columns = line.len();
+    type Input = Vec<Vec<i8>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                line.chars()
+                    .map(|ch| match ch.to_digit(10) {
+                        Some(d) => Ok(d as i8),
+                        None => Err(AocError::parse(i + 1, format!("{:?} isn't a height", ch))),
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
-    println!("height is {}, width is {}", num_rows, num_columns);
-    let mut matrix: Vec<Vec<i8>> = Vec::with_capacity(num_rows);
-    let mut visibles: Vec<Vec<i8>> = Vec::with_capacity(num_rows);
-    for r in 0..num_rows {
-        matrix.push(Vec::with_capacity(num_columns));
-        visibles.push(Vec::with_capacity(num_columns));
-        for _c in 0..num_columns {
-            matrix[r].push(0);
-            visibles[r].push(0);
-        }
+    fn part1(matrix: &Self::Input) -> i32 {
+        count_visible(matrix)
     }
 
-    // Populate the matrix.
-    for (r, line) in lines.iter().enumerate() {
-        for (c, ch) in line.chars().enumerate() {
-            matrix[r][c] = ch as i8 - '0' as i8;
-        }
+    fn part2(matrix: &Self::Input) -> i32 {
+        max_scenic_score(matrix)
     }
+}
 
-    // Find all visible:
+fn count_visible(matrix: &Vec<Vec<i8>>) -> i32 {
+    let num_rows = matrix.len();
+    let num_columns = matrix[0].len();
+    let mut visibles: Vec<Vec<i8>> = vec![vec![0; num_columns]; num_rows];
 
     // From LEFT
     for r in 0..num_rows {
         let mut tallest = -1;
         for c in 0..num_columns {
             if matrix[r][c] > tallest {
-                println!(
-                    "Row ({}x{}): {} is taller than {}",
-                    r, c, matrix[r][c], tallest
-                );
                 visibles[r][c] = 1;
                 tallest = matrix[r][c];
             }
@@ -61,10 +61,6 @@ fn main() {
         let mut tallest = -1;
         for c in (0..num_columns).rev() {
             if matrix[r][c] > tallest {
-                println!(
-                    "Row ({}x{}): {} is taller than {}",
-                    r, c, matrix[r][c], tallest
-                );
                 visibles[r][c] = 1;
                 tallest = matrix[r][c];
             }
@@ -75,10 +71,6 @@ fn main() {
         let mut tallest = -1;
         for r in 0..num_rows {
             if matrix[r][c] > tallest {
-                println!(
-                    "Row ({}x{}): {} is taller than {}",
-                    r, c, matrix[r][c], tallest
-                );
                 visibles[r][c] = 1;
                 tallest = matrix[r][c];
             }
@@ -89,10 +81,6 @@ fn main() {
         let mut tallest = -1;
         for r in (0..num_rows).rev() {
             if matrix[r][c] > tallest {
-                println!(
-                    "Row ({}x{}): {} is taller than {}",
-                    r, c, matrix[r][c], tallest
-                );
                 visibles[r][c] = 1;
                 tallest = matrix[r][c];
             }
@@ -108,18 +96,12 @@ fn main() {
             }
         }
     }
+    return visible;
+}
 
-    println!("Matrix");
-    for r in 0..num_rows {
-        println!("{:?}", matrix[r])
-    }
-
-    println!("\n\nVisibility");
-    for r in 0..num_rows {
-        println!("{:?}", visibles[r])
-    }
-
-    println!("Visible tree: {}", visible);
+fn max_scenic_score(matrix: &Vec<Vec<i8>>) -> i32 {
+    let num_rows = matrix.len();
+    let num_columns = matrix[0].len();
 
     let mut max_score = 0;
     for r in 0..num_rows {
@@ -165,5 +147,5 @@ fn main() {
             }
         }
     }
-    println!("max score: {}", max_score);
+    return max_score;
 }
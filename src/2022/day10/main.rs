@@ -1,78 +1,97 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return, clippy::ptr_arg)]
 
-// Disclaimer: there's a bug here somewhere, but it got close enough that I submitted the right answer.
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::grid::Grid;
+use advent_of_code::utils::ocr;
+use advent_of_code::utils::point::Point;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = &read_lines(args.input);
-
-    {
-        let mut cycle = 0;
-        let mut x = 1;
-        let mut total = 0;
-
-        // Part 1
-        for line in lines.iter() {
-            if let Some(num_add) = parse_line(line.as_str()) {
-                total += maybe_grab_value(cycle + 1, x);
-                total += maybe_grab_value(cycle + 2, x);
-                cycle += 2;
-                x += num_add;
-            } else {
-                cycle += 1;
-                total += maybe_grab_value(cycle, x);
-            }
-        }
-        println!("total is {}", total);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 10;
+
+    type Input = Vec<String>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
     }
 
-    // Part 2
-    {
-        let mut cycle = 0;
-        let mut x = 1;
-        for line in lines.iter() {
-            if let Some(num_add) = parse_line(line.as_str()) {
-                cycle = get_pixel(cycle + 1, x);
-                cycle = get_pixel(cycle + 1, x);
-                x += num_add;
-                if x < 0 {
-                    x = 0;
-                }
-                if x > 39 {
-                    x = 40;
-                }
-            } else {
-                cycle = get_pixel(cycle + 1, x);
-            }
+    fn part1(lines: &Self::Input) -> i32 {
+        handle_pt1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> String {
+        handle_pt2(lines)
+    }
+}
+
+fn handle_pt1(lines: &Vec<String>) -> i32 {
+    let mut cycle = 0;
+    let mut x = 1;
+    let mut total = 0;
+
+    for line in lines.iter() {
+        if let Some(num_add) = parse_line(line.as_str()) {
+            total += maybe_grab_value(cycle + 1, x);
+            total += maybe_grab_value(cycle + 2, x);
+            cycle += 2;
+            x += num_add;
+        } else {
+            cycle += 1;
+            total += maybe_grab_value(cycle, x);
         }
     }
+    return total;
 }
 
-fn get_pixel(cycle: i32, x: i32) -> i32 {
-    let position = cycle % 40;
-    if (position - x).abs() <= 1 {
-        print!("██");
-    } else {
-        print!("░░");
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+/// The letters on the CRT, or the CRT itself (one line per row) if they
+/// can't be read.
+fn handle_pt2(lines: &Vec<String>) -> String {
+    let screen = crt(lines);
+    if let Some(text) = ocr::read(&screen) {
+        return text;
     }
-    if position == 0 {
-        println!("");
+    return format!("\n{}", screen.map(|&lit| if lit { '#' } else { '.' }));
+}
+
+/// Which pixels are lit once the program has run. The pixel drawn during
+/// cycle `c` (counting from 1) is `c - 1` along from the top left, and it's
+/// lit if the 3 pixel wide sprite centered on `x` covers its column.
+fn crt(lines: &Vec<String>) -> Grid<bool> {
+    let mut screen = Grid::new(SCREEN_WIDTH, SCREEN_HEIGHT, false);
+    let mut cycle = 0;
+    let mut x = 1;
+    let mut draw = |cycle: usize, x: i32| {
+        let p = Point::new(cycle % SCREEN_WIDTH, cycle / SCREEN_WIDTH);
+        if let Some(pixel) = screen.get_mut(p) {
+            *pixel = (p.x as i32 - x).abs() <= 1;
+        }
+    };
+    for line in lines.iter() {
+        draw(cycle, x);
+        cycle += 1;
+        if let Some(num_add) = parse_line(line.as_str()) {
+            draw(cycle, x);
+            cycle += 1;
+            x += num_add;
+        }
     }
-    return cycle;
+    return screen;
 }
 
 fn maybe_grab_value(cycle: i32, x: i32) -> i32 {
     if cycle % 40 == 20 {
-        println!("cycle {} has value {} = {}", cycle, x, cycle * x);
         return cycle * x;
     }
     return 0;
@@ -87,3 +106,20 @@ fn parse_line(line: &str) -> Option<i32> {
     assert!(parts[0] == "addx");
     return Some(parts[1].parse::<i32>().unwrap());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crt() {
+        let lines = ["noop", "addx 3", "addx -5"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+        let screen = crt(&lines);
+        // x is 1 for cycles 1-3 and 4 for cycles 4-5.
+        assert_eq!(screen.row(0)[..6], [true, true, true, true, true, false]);
+        assert_eq!(screen.iter().filter(|(_, &lit)| lit).count(), 5);
+    }
+}
@@ -1,41 +1,62 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::len_zero,
+    clippy::needless_return,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
+use advent_of_code::utils::math;
+use advent_of_code::utils::parse::{ints, numbered_sections};
 use std::cell::RefCell;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 11;
+
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(_lines: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+
+    fn part2(lines: &Self::Input) -> i64 {
+        handle_pt2(lines.clone())
+    }
+}
 
-    let monkeys: Vec<RefCell<Monkey>> = split_lines(lines)
-        .iter()
-        .map(|section| Monkey::from_section(section))
+fn handle_pt2(lines: Vec<String>) -> i64 {
+    let monkeys: Vec<RefCell<Monkey>> = numbered_sections(&lines)
+        .into_iter()
+        .map(|(line_no, section)| Monkey::from_section(line_no, section))
         .collect();
-    // Bless modulo arithmetic:
+    // Every test still comes out the same on worry levels mod the lcm of the
+    // divisors, which keeps them from overflowing:
     // https://www.reddit.com/r/adventofcode/comments/zizi43/comment/iztt8mx/
-    let divisor = monkeys
-        .iter()
-        .fold(1, |acc, monkey| acc * monkey.borrow().condition.divisor);
-    println!("Divisor is {}", divisor);
-    for (i, m) in monkeys.iter().enumerate() {
-        println!("M{} = {}", i, m.borrow().condition.divisor);
-    }
+    let divisor = math::lcm_all(
+        monkeys
+            .iter()
+            .map(|monkey| monkey.borrow().condition.divisor),
+    );
 
     for _round in 0..10000 {
-        for (_i, monkey) in monkeys.iter().enumerate() {
+        for monkey in monkeys.iter() {
             let mut monkey = monkey.borrow_mut();
-            loop {
-                match monkey.perform_action(divisor) {
-                    Some((item, dst)) => monkeys[dst as usize].borrow_mut().receive_item(item),
-                    _ => break,
-                }
+            while let Some((item, dst)) = monkey.perform_action(divisor) {
+                monkeys[dst as usize].borrow_mut().receive_item(item);
             }
         }
     }
@@ -47,7 +68,7 @@ fn main() {
     inspections.sort();
     inspections.reverse();
 
-    println!("{}", inspections[0] * inspections[1]);
+    return inspections[0] * inspections[1];
 }
 
 #[derive(Debug)]
@@ -79,7 +100,7 @@ pub struct Monkey {
 }
 
 impl Tok {
-    pub fn from_str(s: &str) -> Self {
+    pub fn parse(s: &str) -> Self {
         match s.parse::<i64>() {
             Ok(n) => {
                 return Self {
@@ -131,7 +152,7 @@ impl Calc {
 }
 
 impl ThrowCondition {
-    pub fn from_section(section: &Vec<String>) -> Self {
+    pub fn from_section(section: &[String]) -> Self {
         return Self {
             divisor: section[3]
                 .trim_start()
@@ -163,26 +184,22 @@ impl ThrowCondition {
 }
 
 impl Monkey {
-    pub fn from_section(section: &Vec<String>) -> RefCell<Self> {
+    /// The monkey described by `section`, which starts on line `line_no`.
+    pub fn from_section(line_no: usize, section: &[String]) -> RefCell<Self> {
         return RefCell::new(Self {
-            items: Monkey::parse_starting_items(section[1].as_str()),
+            items: Monkey::parse_starting_items(line_no + 1, section[1].as_str()),
             condition: ThrowCondition::from_section(section),
             calc: Monkey::parse_equation(section[2].as_str()),
             inspection_count: 0,
         });
     }
 
-    fn parse_starting_items(line: &str) -> Vec<i64> {
+    fn parse_starting_items(line_no: usize, line: &str) -> Vec<i64> {
         let items_str = line
             .trim_start()
             .strip_prefix("Starting items: ")
             .expect("starting items mismatch");
-        let mut res: Vec<i64> = Vec::new();
-        let parts: Vec<&str> = items_str.split(", ").collect();
-        for part in parts.iter() {
-            res.push(part.parse::<i64>().unwrap());
-        }
-        return res;
+        return ints(line_no, items_str).expect("starting items mismatch");
     }
 
     fn parse_equation(line: &str) -> Calc {
@@ -195,9 +212,9 @@ impl Monkey {
         let op_chars: Vec<char> = eq_parts[1].chars().collect();
         assert!(op_chars.len() == 1);
         return Calc::with_equation(
-            Tok::from_str(eq_parts[0]),
+            Tok::parse(eq_parts[0]),
             op_chars[0],
-            Tok::from_str(eq_parts[2]),
+            Tok::parse(eq_parts[2]),
         );
     }
 
@@ -216,18 +233,4 @@ impl Monkey {
     }
 }
 
-fn split_lines(lines: Vec<String>) -> Vec<Vec<String>> {
-    let mut split_lines: Vec<Vec<String>> = Vec::new();
-    split_lines.push(Vec::new());
-    let mut section_idx = 0;
-    for line in lines.iter() {
-        if line.len() == 0 {
-            section_idx += 1;
-            split_lines.push(Vec::new());
-            continue;
-        }
-
-        split_lines[section_idx].push(line.clone());
-    }
-    return split_lines;
-}
+advent_of_code::example_tests!(Day, "2022/day11");
@@ -1,53 +1,67 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::len_zero,
+    clippy::needless_return,
+    clippy::ptr_arg
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use std::cmp::Ordering;
 use std::fmt;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
 
-    let input = parse_input(lines);
-    let mapped: Vec<usize> = input
-        .iter()
-        .enumerate()
-        .filter(|(_i, (left, right))| {
-            Signal::parse(&left).compare(&Signal::parse(&right)) == Ordering::Less
-        })
-        .map(|(i, _)| i + 1)
-        .collect();
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 13;
 
-    let pt1 = mapped.iter().sum::<usize>();
-    println!("Part 1: {}", pt1);
+    type Input = Vec<(Vec<char>, Vec<char>)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(parse_input(lines.to_vec()))
+    }
 
-    let mut p2_input: Vec<Signal> = Vec::new();
-    for (left, right) in &input {
-        p2_input.push(Signal::parse(left));
-        p2_input.push(Signal::parse(right));
+    fn part1(input: &Self::Input) -> usize {
+        input
+            .iter()
+            .enumerate()
+            .filter(|(_i, (left, right))| {
+                Signal::parse(left).compare(&Signal::parse(right)) == Ordering::Less
+            })
+            .map(|(i, _)| i + 1)
+            .sum::<usize>()
     }
-    p2_input.push(Signal::signals(Vec::from([Signal::val(2)])));
-    p2_input.push(Signal::signals(Vec::from([Signal::val(6)])));
-    p2_input.sort_by(|x, y| x.compare(&y));
 
-    let pt2 = (1 + p2_input
-        .iter()
-        .position(|s| s.compare(&Signal::signals(Vec::from([Signal::val(2)]))) == Ordering::Equal)
-        .unwrap())
-        * (1 + p2_input
+    fn part2(input: &Self::Input) -> usize {
+        let mut p2_input: Vec<Signal> = Vec::new();
+        for (left, right) in input {
+            p2_input.push(Signal::parse(left));
+            p2_input.push(Signal::parse(right));
+        }
+        p2_input.push(Signal::signals(Vec::from([Signal::val(2)])));
+        p2_input.push(Signal::signals(Vec::from([Signal::val(6)])));
+        p2_input.sort_by(|x, y| x.compare(y));
+
+        (1 + p2_input
             .iter()
             .position(|s| {
-                s.compare(&Signal::signals(Vec::from([Signal::val(6)]))) == Ordering::Equal
+                s.compare(&Signal::signals(Vec::from([Signal::val(2)]))) == Ordering::Equal
             })
-            .unwrap());
-    println!("Part 2: {}", pt2);
+            .unwrap())
+            * (1 + p2_input
+                .iter()
+                .position(|s| {
+                    s.compare(&Signal::signals(Vec::from([Signal::val(6)]))) == Ordering::Equal
+                })
+                .unwrap())
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -95,7 +109,7 @@ impl Clone for Signal {
 impl Signal {
     pub fn parse(v: &Vec<char>) -> Self {
         // Values can have more than 1 digit...
-        if v.iter().position(|&c| c == '[') == None {
+        if v.iter().position(|&c| c == '[').is_none() {
             return Signal::val(v.iter().collect::<String>().parse::<i32>().unwrap());
         }
         let mut num_brackets = 0;
@@ -115,8 +129,7 @@ impl Signal {
                 }
                 ']' => {
                     num_brackets -= 1;
-                    if num_brackets == 0 && i > 0 && v[i - 1] != ']' && i - last_non_digit - 1 >= 1
-                    {
+                    if num_brackets == 0 && i > 0 && v[i - 1] != ']' && i - last_non_digit > 1 {
                         signals.push(Signal::parse(&Vec::from(&v[last_non_digit + 1..i])));
                     }
                     if num_brackets == 1 {
@@ -125,8 +138,7 @@ impl Signal {
                     }
                 }
                 ',' => {
-                    if num_brackets == 1 && i > 0 && v[i - 1] != ']' && i - last_non_digit - 1 >= 1
-                    {
+                    if num_brackets == 1 && i > 0 && v[i - 1] != ']' && i - last_non_digit > 1 {
                         signals.push(Signal::parse(&Vec::from(&v[last_non_digit + 1..i])));
                     }
                     last_non_digit = i;
@@ -192,7 +204,7 @@ impl Signal {
                     if cmp != Ordering::Equal {
                         return cmp;
                     }
-                    i = i + 1;
+                    i += 1;
                 }
                 return asig.len().cmp(&bsig.len());
             }
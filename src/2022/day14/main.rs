@@ -1,35 +1,66 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
 use std::collections::HashMap;
 
 const ROCK: char = '#';
 const EMPTY: char = ' ';
 const SAND: char = '.';
 
+/// How many grains come to rest between frames of `--visualize`.
+const GRAINS_PER_FRAME: i32 = 25;
+
 static START: Point<i32> = Point::<i32> { x: 500, y: 0 };
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 14;
+
+    type Input = HashMap<i32, HashMap<i32, char>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(parse_input(&lines.to_vec()))
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        drop_sand_pt1(&mut input.clone(), |_, _| {})
+    }
 
-    {
-        let mut input = parse_input(&lines);
-        let pt1 = drop_sand_pt1(&mut input);
-        println!("Part 1: {}", pt1);
+    fn part2(input: &Self::Input) -> i32 {
+        drop_sand_pt2(&mut input.clone(), |_, _| {})
     }
-    {
-        let mut input = parse_input(&lines);
-        let pt2 = drop_sand_pt2(&mut input);
-        println!("Part 2: {}", pt2);
+
+    fn visualize(input: &Self::Input, viz: &mut Visualizer) {
+        let mut m = input.clone();
+        drop_sand_pt1(&mut m, |m, inserted| {
+            if inserted % GRAINS_PER_FRAME == 0 {
+                viz.frame(&draw_map(m));
+            }
+        });
+        viz.frame(&draw_map(&m));
+
+        let mut m = input.clone();
+        drop_sand_pt2(&mut m, |m, inserted| {
+            if inserted % GRAINS_PER_FRAME == 0 {
+                viz.frame(&draw_map(m));
+            }
+        });
+        viz.frame(&draw_map(&m));
     }
 }
 
@@ -66,36 +97,34 @@ fn point_range_inclusive(from: Point<i32>, to: Point<i32>) -> Vec<Point<i32>> {
     return res;
 }
 
-fn _print_map(m: &mut HashMap<i32, HashMap<i32, char>>) {
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-    for x in m.keys() {
-        left.push(x);
-        right.push(x);
-        left = Vec::from([*left.iter().min().unwrap()]);
-        right = Vec::from([*right.iter().max().unwrap()]);
-    }
-    let leftest = *left[0];
-    let rightest = *right[0];
-    let lowest = get_lowest_point(m);
-    println!(
-        "LEFT: {}, RIGHT: {}, TOP: 0, BOTTOM: {}",
-        leftest, rightest, lowest
-    );
-
-    for y in 0..lowest {
-        for x in leftest..=rightest {
-            if x == 500 && y == 0 {
-                print!("S");
-            } else {
-                print!("{}", get(m, Point::<i32> { x: x, y: y }));
-            }
+/// The rock and sand so far, with the sand's source marked.
+fn draw_map(m: &HashMap<i32, HashMap<i32, char>>) -> Frame {
+    let filled = m.iter().flat_map(|(&x, by_row)| {
+        by_row
+            .iter()
+            .filter(|(_, &c)| c != EMPTY)
+            .map(move |(&y, _)| Point::new(x, y))
+    });
+    let (min, max) = viz::bounds(filled.chain([START])).unwrap();
+    return viz::draw(min, max, |p| {
+        let (x, y) = (p.x as i32, p.y as i32);
+        if x == START.x && y == START.y {
+            return Cell::colored('S', Rgb(255, 80, 80));
         }
-        println!("");
-    }
+        match m.get(&x).and_then(|by_row| by_row.get(&y)) {
+            Some(&ROCK) => Cell::colored(ROCK, Rgb(130, 130, 140)),
+            Some(&SAND) => Cell::colored(SAND, Rgb(230, 190, 90)),
+            _ => Cell::from(EMPTY),
+        }
+    });
 }
 
-fn drop_sand_pt1(m: &mut HashMap<i32, HashMap<i32, char>>) -> i32 {
+/// `on_rest` gets the map and how many grains there are each time one comes
+/// to rest.
+fn drop_sand_pt1<F>(m: &mut HashMap<i32, HashMap<i32, char>>, mut on_rest: F) -> i32
+where
+    F: FnMut(&HashMap<i32, HashMap<i32, char>>, i32),
+{
     let mut curr: Point<i32> = START;
     let max_y = get_lowest_point(m);
     let mut inserted = 0;
@@ -133,11 +162,15 @@ fn drop_sand_pt1(m: &mut HashMap<i32, HashMap<i32, char>>) -> i32 {
 
         insert(m, curr, SAND);
         inserted += 1;
+        on_rest(m, inserted);
         curr = START;
     }
 }
 
-fn drop_sand_pt2(m: &mut HashMap<i32, HashMap<i32, char>>) -> i32 {
+fn drop_sand_pt2<F>(m: &mut HashMap<i32, HashMap<i32, char>>, mut on_rest: F) -> i32
+where
+    F: FnMut(&HashMap<i32, HashMap<i32, char>>, i32),
+{
     let max_y = get_lowest_point(m) + 2;
     let mut inserted = 0;
     let mut curr: Point<i32> = START;
@@ -174,6 +207,7 @@ fn drop_sand_pt2(m: &mut HashMap<i32, HashMap<i32, char>>) -> i32 {
 
         insert(m, curr, SAND);
         inserted += 1;
+        on_rest(m, inserted);
         if curr == START {
             return inserted;
         }
@@ -216,12 +250,12 @@ fn get_lowest_point(m: &mut HashMap<i32, HashMap<i32, char>>) -> i32 {
 }
 
 fn get(m: &mut HashMap<i32, HashMap<i32, char>>, p: Point<i32>) -> char {
-    let by_row = m.entry(p.x).or_insert(HashMap::new());
+    let by_row = m.entry(p.x).or_default();
     return *by_row.entry(p.y).or_insert(EMPTY);
 }
 
 fn insert(m: &mut HashMap<i32, HashMap<i32, char>>, p: Point<i32>, c: char) {
-    let by_row = m.entry(p.x).or_insert(HashMap::new());
+    let by_row = m.entry(p.x).or_default();
     *by_row.entry(p.y).or_insert(c) = c;
 }
 
@@ -1,156 +1,91 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::interval::{Inclusive, IntervalSet};
 use advent_of_code::utils::point::Point;
-use clap::Parser;
-use regex::Regex;
 use std::collections::HashSet;
-// use std::sync::Mutex;
-// use std::thread;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    part_1(&lines);
-    //    part_2(&lines);
+    advent_of_code::solution::run::<Day>();
 }
 
-fn part_1(lines: &Vec<String>) {
-    let sensors = lines
-        .iter()
-        .map(|line| Sensor::parse(line))
-        .collect::<Vec<Sensor>>();
-
-    let mut want_set: HashSet<i64> = HashSet::new();
-    let want_y = 2000000;
-
-    for s in &sensors {
-        let y_dist = distance(
-            s.location,
-            Point::<i64> {
-                x: s.location.x,
-                y: want_y,
-            },
-        );
-        if s.dist < y_dist {
-            continue;
-        }
+pub struct Day;
 
-        let x_radius = s.dist - y_dist;
-        for x in (s.location.x - x_radius)..(s.location.x + x_radius) {
-            want_set.insert(x);
-        }
-    }
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 15;
 
-    println!("Part 1: {:?}", want_set.len());
-}
-
-const MULT: i64 = 4000000;
-
-mod shared {
-    use std::sync::{Arc, LockResult, RwLock, RwLockReadGuard, RwLockWriteGuard};
+    type Input = Vec<Sensor>;
+    type Part1 = usize;
+    type Part2 = i64;
 
-    pub struct Lock<T> {
-        inner: Arc<RwLock<T>>,
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Sensor::parse(i + 1, line))
+            .collect()
     }
 
-    impl<T> Lock<T> {
-        pub fn new(val: T) -> Self {
-            Self {
-                inner: Arc::new(RwLock::new(val)),
-            }
-        }
-
-        pub fn write(&self) -> LockResult<RwLockWriteGuard<'_, T>> {
-            self.inner.write()
-        }
-
-        pub fn read(&self) -> LockResult<RwLockReadGuard<'_, T>> {
-            self.inner.read()
-        }
-
-        pub fn read_only(&self) -> ReadOnly<T> {
-            ReadOnly {
-                inner: self.inner.clone(),
-            }
-        }
+    fn part1(sensors: &Self::Input) -> usize {
+        no_beacon_count(sensors, ROW)
     }
 
-    pub struct ReadOnly<T> {
-        inner: Arc<RwLock<T>>,
+    fn part2(sensors: &Self::Input) -> i64 {
+        let beacon = distress_beacon(sensors, BOUND).expect("every spot is covered");
+        tuning_frequency(beacon)
     }
+}
+
+/// The row part 1 asks about.
+const ROW: i64 = 2000000;
+/// How far along each axis (from 0) the distress beacon can be.
+const BOUND: i64 = 4000000;
 
-    impl<T> ReadOnly<T> {
-        pub fn read(&self) -> LockResult<RwLockReadGuard<'_, T>> {
-            self.inner.read()
+/// The x values on row `y` that some sensor can see.
+fn row_coverage(sensors: &Vec<Sensor>, y: i64) -> IntervalSet<i64> {
+    let mut covered = IntervalSet::new();
+    for s in sensors {
+        let y_dist = (s.location.y - y).abs();
+        if s.dist < y_dist {
+            continue;
         }
+
+        let x_radius = s.dist - y_dist;
+        covered.insert(Inclusive::new(s.location.x - x_radius, s.location.x + x_radius).into());
     }
+    return covered;
 }
 
-/*
-fn part_2(lines: &Vec<String>) {
-    let sensors = lines
+/// The places on row `y` a beacon can't be, which are the ones sensors can
+/// see minus the beacons they found there.
+fn no_beacon_count(sensors: &Vec<Sensor>, y: i64) -> usize {
+    let covered = row_coverage(sensors, y);
+    let beacons = sensors
         .iter()
-        .map(|line| Sensor::parse(line))
-        .collect::<Vec<Sensor>>();
-    let borrowed_sensors = shared::Lock::new(&sensors);
-
-    let value = Mutex::new(0);
-    let num_threads = 1000;
-    for i in 0..num_threads {
-        let view = borrowed_sensors.read_only();
-        std::thread::spawn(move || {
-            let start = (MULT / num_threads) * i;
-            let end = start + (MULT / num_threads);
-            let num_iterations = 1000000;
-            for x in start..end {
-                for y in start..end {
-                    let curr = Point::<i64>{x:x,y:y};
-                    let mut possible: bool = true;
-                    for s in sensors {
-                        if s.location.dist(curr) < s.dist || s.beacon == curr {
-                            possible = false;
-                            break;
-                        }
-                    }
-                    if possible {
-                        *value.lock().unwrap() = tuning_frequency(curr);
-                        return;
-                    }
-
-                    if y % num_iterations == 0 {
-                        if *value.lock().unwrap() > 0 {
-                            return;
-                        }
-                        println!(
-                            "{}: {}/{} = {}%",
-                            i,
-                            x * MULT + y,
-                            MULT * MULT,
-                            ((x * MULT + y) as f64) / ((MULT * MULT) as f64)
-                        );
-                    }
-                }
-            }
-        });
-    }
-    println!("Part 2: {:?}", value.lock().unwrap());
+        .map(|s| s.beacon)
+        .filter(|b| b.y == y && covered.contains(b.x))
+        .collect::<HashSet<Point<i64>>>();
+    return covered.len() as usize - beacons.len();
 }
-*/
 
-fn distance(p1: Point<i64>, p2: Point<i64>) -> i64 {
-    (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
+/// The one place with `x` and `y` both between 0 and `bound` that no sensor
+/// can see, found by going down the rows looking for one with a gap.
+fn distress_beacon(sensors: &Vec<Sensor>, bound: i64) -> Option<Point<i64>> {
+    let search = IntervalSet::from(Inclusive::new(0, bound));
+    for y in 0..=bound {
+        if let Some(x) = search.difference(&row_coverage(sensors, y)).min() {
+            return Some(Point::new(x, y));
+        }
+    }
+    return None;
 }
 
+/// What the distress beacon's position is turned into for the answer.
 fn tuning_frequency(p: Point<i64>) -> i64 {
-    p.x * MULT + p.y
+    p.x * 4000000 + p.y
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -161,45 +96,56 @@ pub struct Sensor {
 }
 
 impl Sensor {
-    pub fn parse(s: &str) -> Self {
-        let sensor_re = Regex::new(
-            r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)",
-        )
-        .unwrap();
-        let mut s_x = 0;
-        let mut s_y = 0;
-        let mut b_x = 0;
-        let mut b_y = 0;
-
-        assert!(sensor_re.is_match(s));
-        for m in sensor_re.captures_iter(s) {
-            for (i, capt) in m.iter().enumerate() {
-                if let Some(sub) = capt {
-                    if i == 0 {
-                        continue;
-                    }
-
-                    let v = str2i64(sub.as_str());
-                    match i {
-                        1 => s_x = v,
-                        2 => s_y = v,
-                        3 => b_x = v,
-                        4 => b_y = v,
-                        _ => (),
-                    }
-                }
-            }
-        }
+    pub fn parse(line_no: usize, s: &str) -> Result<Self, AocError> {
+        let (s_x, s_y, b_x, b_y) = scan!(
+            line_no,
+            s,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}" => i64, i64, i64, i64
+        )?;
         let location = Point::<i64> { x: s_x, y: s_y };
         let closest_beacon = Point::<i64> { x: b_x, y: b_y };
-        return Self {
+        return Ok(Self {
             location: location,
             beacon: closest_beacon,
-            dist: distance(location, closest_beacon),
-        };
+            dist: location.manhattan(closest_beacon),
+        });
     }
 }
 
-fn str2i64(s: &str) -> i64 {
-    return s.parse::<i64>().unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<Sensor> {
+        let sensors = [
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15",
+            "Sensor at x=9, y=16: closest beacon is at x=10, y=16",
+            "Sensor at x=13, y=2: closest beacon is at x=15, y=3",
+            "Sensor at x=12, y=14: closest beacon is at x=10, y=16",
+            "Sensor at x=10, y=20: closest beacon is at x=10, y=16",
+            "Sensor at x=14, y=17: closest beacon is at x=10, y=16",
+            "Sensor at x=8, y=7: closest beacon is at x=2, y=10",
+            "Sensor at x=2, y=0: closest beacon is at x=2, y=10",
+            "Sensor at x=0, y=11: closest beacon is at x=2, y=10",
+            "Sensor at x=20, y=14: closest beacon is at x=25, y=17",
+            "Sensor at x=17, y=20: closest beacon is at x=21, y=22",
+            "Sensor at x=16, y=7: closest beacon is at x=15, y=3",
+            "Sensor at x=14, y=3: closest beacon is at x=15, y=3",
+            "Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+        ]
+        .map(String::from);
+        return Day::parse(&sensors).unwrap();
+    }
+
+    #[test]
+    fn test_no_beacon_count() {
+        assert_eq!(no_beacon_count(&example(), 10), 26);
+    }
+
+    #[test]
+    fn test_distress_beacon() {
+        let beacon = distress_beacon(&example(), 20);
+        assert_eq!(beacon, Some(Point::new(14, 11)));
+        assert_eq!(tuning_frequency(beacon.unwrap()), 56000011);
+    }
 }
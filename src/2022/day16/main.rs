@@ -1,56 +1,43 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
-use std::cell::RefCell;
-use std::cmp::Ordering;
+#![allow(clippy::needless_range_loop, clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::search::bfs;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::fmt;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
-
 fn main() {
-    //   Time:        41     96     88     94
-    //Distance:   214   1789   1127   1055
-
-    {
-        // 1180 is too low.
-        // 1250 is too low.
-        // 1600 is too low.
-        println!("Part 1: {}", solve_pt1(&lines));
-    }
+    advent_of_code::solution::run::<Day>();
 }
-const MINUTES: usize = 30;
-const START_VALVE: &str = "AA";
 
-fn solve_pt1(lines: &Vec<String>) -> usize {
-    let mut valves: HashMap<String, RefCell<Valve>> = HashMap::new();
-    for line in lines {
-        let (name, valve) = Valve::parse(&line);
-        valves.entry(name).or_insert(valve);
-    }
+pub struct Day;
 
-    let dists = create_dist_grid(&valves);
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 16;
 
-    for key in valves.keys() {
-        println!("{}: {}", key, valves[key].borrow());
+    type Input = HashMap<String, Valve>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Valve::parse(i + 1, line))
+            .collect()
     }
 
-    let mut closed: HashSet<String> = HashSet::new();
-    for (name, valve) in &valves {
-        let valve = valve.borrow();
-        if valve.flow_rate > 0 {
-            closed.insert(name.clone());
-        }
+    fn part1(valves: &Self::Input) -> usize {
+        let tunnels = create_dist_grid(valves, "AA");
+        most_pressure(&tunnels, 30)
     }
 
-    return dfs(&mut valves, &mut closed, &dists);
+    fn part2(valves: &Self::Input) -> usize {
+        let tunnels = create_dist_grid(valves, "AA");
+        most_pressure_with_elephant(&tunnels, 26)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,41 +47,24 @@ pub struct Valve {
 }
 
 impl Valve {
-    pub fn new(flow_rate: usize, leads_to: Vec<String>) -> Self {
-        Self {
-            flow_rate: flow_rate,
-            leads_to: leads_to,
-        }
-    }
-
-    pub fn parse(s: &str) -> (String, RefCell<Self>) {
-        let valve_re = Regex::new(
-            r"Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z ,]+)",
-        )
-        .unwrap();
-        let mut name = String::from("");
-        let mut leads_to: Vec<String> = Vec::new();
-        let mut flow_rate = 0;
-        assert!(valve_re.is_match(s), "Improper input? {}", s);
-        for m in valve_re.captures_iter(s) {
-            for (i, capt) in m.iter().enumerate() {
-                if let Some(sub) = capt {
-                    match i {
-                        1 => name = String::from(sub.as_str()),
-                        2 => flow_rate = sub.as_str().parse::<usize>().unwrap(),
-                        3 => {
-                            leads_to = sub
-                                .as_str()
-                                .split(", ")
-                                .map(|v| String::from(v))
-                                .collect::<Vec<String>>();
-                        }
-                        _ => (),
-                    }
-                }
-            }
-        }
-        return (name, RefCell::new(Valve::new(flow_rate, leads_to)));
+    pub fn parse(line_no: usize, s: &str) -> Result<(String, Self), AocError> {
+        let (name, flow_rate, tunnels) =
+            scan!(line_no, s, "Valve {} has flow rate={}; {}" => String, usize, String)?;
+        // "tunnel leads to valve GG" or "tunnels lead to valves DD, II, BB".
+        let Some(start) = tunnels.find(|c: char| c.is_ascii_uppercase()) else {
+            return Err(AocError::parse(line_no, "no tunnels out of the valve"));
+        };
+        let leads_to = tunnels[start..]
+            .split(", ")
+            .map(String::from)
+            .collect::<Vec<String>>();
+        return Ok((
+            name,
+            Self {
+                flow_rate,
+                leads_to,
+            },
+        ));
     }
 }
 
@@ -104,225 +74,111 @@ impl fmt::Display for Valve {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct State {
-    closed: HashSet<String>,
-    opened: Vec<(String, usize)>,
-    loc: String,
-    flow: usize,
-    minute: usize,
-    max_minutes: usize,
-}
-
-impl fmt::Display for State {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "State @ {} (elapsed = {}m) (flow = {})\n  - Opened: {:?}\n  - Closed {:?}",
-            self.loc, self.minute, self.flow, self.opened, self.closed,
-        )
-    }
+fn dist(valves: &HashMap<String, Valve>, start: &str, end: &str) -> usize {
+    bfs([String::from(start)], |v| valves[v].leads_to.clone())
+        .distance(&String::from(end))
+        .expect("every valve can reach every other")
 }
 
-fn dist(valves: &HashMap<String, RefCell<Valve>>, start: &str, end: &str) -> usize {
-    if start == end {
-        return 0;
-    }
-    let mut visited: HashSet<String> = HashSet::new();
-    let mut to_visit: Vec<(String, usize)> = Vec::new();
-    to_visit.push((String::from(start), 0));
-    while to_visit.len() > 0 {
-        let (curr, partial_distance) = to_visit.pop().unwrap();
-        visited.insert(curr.clone());
-        for neighbor in &valves[&curr].borrow().leads_to {
-            if neighbor == end {
-                return partial_distance + 1;
-            }
-            if !visited.contains(neighbor) {
-                visited.insert(neighbor.clone());
-                to_visit.push((String::from(neighbor), partial_distance + 1));
-            }
-        }
-    }
-
-    assert!(false);
-    return 0;
+/// Just the valves worth opening, numbered so that a set of them fits in a
+/// bitmask, and how many minutes it takes to walk between them.
+#[derive(Debug, Clone)]
+pub struct Tunnels {
+    flow_rates: Vec<usize>,
+    /// `dists[i][j]` is from valve `i` to valve `j`.
+    dists: Vec<Vec<usize>>,
+    /// From the valve everyone starts at to each valve.
+    from_start: Vec<usize>,
 }
 
-fn get_dist(dists: &HashMap<String, usize>, start: &str, end: &str) -> usize {
-    if start == end {
-        return 0;
-    }
-    let key = match start.cmp(end) {
-        Ordering::Less | Ordering::Equal => format!("{}:{}", start, end),
-        Ordering::Greater => format!("{}:{}", end, start),
+fn create_dist_grid(valves: &HashMap<String, Valve>, start: &str) -> Tunnels {
+    let mut useful = valves
+        .iter()
+        .filter(|(_, valve)| valve.flow_rate > 0)
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<&str>>();
+    useful.sort();
+    assert!(useful.len() <= 64, "too many valves for a u64 of them");
+
+    let from = |a: &str| {
+        useful
+            .iter()
+            .map(|b| dist(valves, a, b))
+            .collect::<Vec<usize>>()
+    };
+    return Tunnels {
+        flow_rates: useful.iter().map(|name| valves[*name].flow_rate).collect(),
+        dists: useful.iter().map(|a| from(a)).collect(),
+        from_start: from(start),
     };
-    return dists[&key];
 }
 
-fn create_dist_grid(valves: &HashMap<String, RefCell<Valve>>) -> HashMap<String, usize> {
-    let mut dist_grid: HashMap<String, usize> = HashMap::new();
-    let mut keys: Vec<String> = Vec::new();
-    for k in valves.keys() {
-        keys.push(k.clone());
-    }
-    keys.sort();
-
-    for i in 0..keys.len() {
-        for j in i..keys.len() {
-            if (&keys[i] == START_VALVE || valves[&keys[i]].borrow().flow_rate > 0)
-                && valves[&keys[j]].borrow().flow_rate > 0
-            {
-                let distance = dist(valves, &keys[i], &keys[j]);
-                *dist_grid
-                    .entry(format!("{}:{}", keys[i], keys[j]))
-                    .or_insert(distance) = distance;
-            }
-        }
-    }
+/// The most pressure that can be released in `minutes` by opening exactly
+/// the valves in each set (as a bitmask), for every set there's time to open.
+fn best_per_opened(tunnels: &Tunnels, minutes: usize) -> HashMap<u64, usize> {
+    let mut best = HashMap::new();
+    visit(tunnels, &tunnels.from_start, minutes, 0, 0, &mut best);
+    return best;
+}
 
-    let mut keys: Vec<String> = Vec::new();
-    for k in dist_grid.keys() {
-        keys.push(k.clone());
-    }
-    keys.sort();
-    println!("Distances:");
-    for k in keys {
-        let parts = k.split(":").collect::<Vec<&str>>();
-        let flow_rate = valves[parts[1]].borrow().flow_rate;
-        let mut potential = 0;
-        if dist_grid[&k] <= MINUTES {
-            potential = (MINUTES - dist_grid[&k]) * flow_rate;
+fn visit(
+    tunnels: &Tunnels,
+    dists: &[usize],
+    minutes_left: usize,
+    opened: u64,
+    pressure: usize,
+    best: &mut HashMap<u64, usize>,
+) {
+    let most = best.entry(opened).or_insert(0);
+    *most = pressure.max(*most);
+
+    for next in 0..tunnels.flow_rates.len() {
+        // Walking there and then a minute to open it.
+        let cost = dists[next] + 1;
+        if opened & (1 << next) != 0 || cost >= minutes_left {
+            continue;
         }
-        println!(
-            " - {} -> {} (flow={}): {} --> {}",
-            parts[0], parts[1], flow_rate, dist_grid[&k], potential,
+        let left = minutes_left - cost;
+        visit(
+            tunnels,
+            &tunnels.dists[next],
+            left,
+            opened | (1 << next),
+            pressure + left * tunnels.flow_rates[next],
+            best,
         );
     }
-    return dist_grid;
 }
 
-impl State {
-    fn next(
-        &mut self,
-        valves: &HashMap<String, RefCell<Valve>>,
-        dists: &HashMap<String, usize>,
-        max_flow: usize,
-    ) -> Vec<State> {
-        if self.minute >= self.max_minutes {
-            return Vec::new();
-        }
-
-        if self.closed.len() == 0 {
-            return Vec::new();
-        }
-
-        let mut potential_max_flow = self.flow;
-        let mut mins_left = self.max_minutes - self.minute;
-        for closed in &self.closed {
-            let dist = get_dist(dists, self.loc.as_str(), closed.as_str());
-            if dist < mins_left {
-                potential_max_flow += valves[closed].borrow().flow_rate * (mins_left - dist);
-            }
-        }
-        if potential_max_flow <= max_flow {
-            println!(
-                "Potential is {}, which is less than max {}",
-                potential_max_flow, max_flow
-            );
-            return Vec::new();
-        }
-
-        let mut res: Vec<State> = Vec::new();
-        // Then we move onto the next valve.
-        for closed_valve in &self.closed {
-            if closed_valve == &self.loc {
-                continue;
-            }
-
-            let mut next = self.clone();
-            next.loc = closed_valve.clone();
-            let distance = get_dist(dists, &self.loc, closed_valve);
-            if distance > mins_left {
-                continue;
-            }
-            next.minute += distance;
-
-            // Open this valve if we can.
-            if next.closed.contains(&next.loc) && next.minute < next.max_minutes {
-                next.minute += 1;
-                mins_left = next.max_minutes - next.minute;
-                next.flow += valves[&next.loc].borrow().flow_rate * mins_left;
-                next.closed.remove(&next.loc);
-                next.opened.push((
-                    format!(
-                        "{} ({})",
-                        next.loc.clone(),
-                        valves[&next.loc].borrow().flow_rate
-                    ),
-                    next.minute,
-                ));
-                println!("open {}", next.loc);
-            }
-            res.push(next);
-        }
-
-        res.sort_by(|a, b| {
-            let by_flow = a.flow.cmp(&b.flow);
-            if by_flow != Ordering::Equal {
-                return by_flow;
-            }
-            let by_dist = b.minute.cmp(&a.minute);
-            return by_dist;
-        });
-        println!("......res");
-        for s in &res {
-            println!("...{}", s);
-        }
-
-        return res;
-    }
+fn most_pressure(tunnels: &Tunnels, minutes: usize) -> usize {
+    *best_per_opened(tunnels, minutes).values().max().unwrap()
 }
 
-fn dfs(
-    valves: &HashMap<String, RefCell<Valve>>,
-    closed: &mut HashSet<String>,
-    dists: &HashMap<String, usize>,
-) -> usize {
-    let mut potentials: Vec<State> = Vec::new();
-    potentials.push(State {
-        closed: closed.clone(),
-        opened: Vec::new(),
-        loc: String::from(START_VALVE),
-        flow: 0,
-        minute: 0,
-        max_minutes: MINUTES,
-    });
-
-    let mut max_flow: usize = 0;
-    let mut curr: State;
-    while potentials.len() > 0 {
-        curr = potentials.pop().unwrap();
-        println!("MAX IS {}\n{}", max_flow, curr);
-        let next = curr.next(valves, dists, max_flow);
-        for n in &next {
-            if n.flow > max_flow {
-                max_flow = n.flow;
+/// With an elephant opening valves at the same time, the best is whatever
+/// two sets with no valves in common add up to.
+fn most_pressure_with_elephant(tunnels: &Tunnels, minutes: usize) -> usize {
+    let best = best_per_opened(tunnels, minutes)
+        .into_iter()
+        .collect::<Vec<(u64, usize)>>();
+    let mut most = 0;
+    for (i, &(mine, me)) in best.iter().enumerate() {
+        for &(theirs, elephant) in &best[i..] {
+            if mine & theirs == 0 {
+                most = most.max(me + elephant);
             }
-            potentials.push(n.clone());
         }
     }
-
-    return max_flow;
+    return most;
 }
 
+advent_of_code::example_tests!(Day, "2022/day16");
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn example_works() {
-        let mut input = Vec::from([
+    fn example() -> HashMap<String, Valve> {
+        let lines = [
             "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB",
             "Valve BB has flow rate=13; tunnels lead to valves CC, AA",
             "Valve CC has flow rate=2; tunnels lead to valves DD, BB",
@@ -333,38 +189,40 @@ mod tests {
             "Valve HH has flow rate=22; tunnel leads to valve GG",
             "Valve II has flow rate=0; tunnels lead to valves AA, JJ",
             "Valve JJ has flow rate=21; tunnel leads to valve II",
-        ])
-        .iter()
-        .map(|&s| String::from(s))
-        .collect::<Vec<String>>();
+        ]
+        .map(String::from);
+        return Day::parse(&lines).unwrap();
+    }
 
-        assert_eq!(1651, solve_pt1(&input));
+    #[test]
+    fn example_works() {
+        let tunnels = create_dist_grid(&example(), "AA");
+        assert_eq!(1651, most_pressure(&tunnels, 30));
+        assert_eq!(1707, most_pressure_with_elephant(&tunnels, 26));
+
+        // With no time to get anywhere and open anything.
+        assert_eq!(0, most_pressure(&tunnels, 1));
+        // Starting at JJ leaves it closed, since it's a minute to open.
+        let tunnels = create_dist_grid(&example(), "JJ");
+        assert_eq!(0, most_pressure(&tunnels, 1));
+        assert_eq!(21, most_pressure(&tunnels, 2));
     }
 
     #[test]
     fn dist_works() {
-        let lines = Vec::from([
-            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB",
-            "Valve BB has flow rate=13; tunnels lead to valves CC, AA",
-            "Valve CC has flow rate=2; tunnels lead to valves DD, BB",
-            "Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE",
-            "Valve EE has flow rate=3; tunnels lead to valves FF, DD",
-            "Valve FF has flow rate=0; tunnels lead to valves EE, GG",
-            "Valve GG has flow rate=0; tunnels lead to valves FF, HH",
-            "Valve HH has flow rate=22; tunnel leads to valve GG",
-            "Valve II has flow rate=0; tunnels lead to valves AA, JJ",
-            "Valve JJ has flow rate=21; tunnel leads to valve II",
-        ])
-        .iter()
-        .map(|&s| String::from(s))
-        .collect::<Vec<String>>();
-        let mut valves: HashMap<String, RefCell<Valve>> = HashMap::new();
-        for line in &lines {
-            let (name, valve) = Valve::parse(&line);
-            valves.entry(name).or_insert(valve);
-        }
-
+        let valves = example();
         assert_eq!(7, dist(&valves, "JJ", "HH"));
         assert_eq!(1, dist(&valves, "AA", "DD"));
+
+        let tunnels = create_dist_grid(&valves, "AA");
+        // BB, CC, DD, EE, HH and JJ.
+        assert_eq!(tunnels.flow_rates, vec![13, 2, 20, 3, 22, 21]);
+        assert_eq!(tunnels.from_start, vec![1, 2, 1, 2, 5, 2]);
+    }
+
+    #[test]
+    fn parse_rejects_nonsense() {
+        assert!(Valve::parse(3, "Valve AA has flow rate=x; tunnels lead to valves BB").is_err());
+        assert!(Valve::parse(3, "Valve AA has flow rate=1; no tunnels").is_err());
     }
 }
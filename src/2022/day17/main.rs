@@ -1,32 +1,43 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use std::fmt;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::ptr_arg
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::cycle;
+use advent_of_code::utils::point::Point;
 
 fn main() {
-    let args = Args::parse();
-    let dirs = parse_directions(&args.input);
+    advent_of_code::solution::run::<Day>();
+}
 
-    {
-        let pt1 = tetris_pt1(&dirs, 2022);
-        // 3024 is too low.
-        println!("Part 1: {}", pt1);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 17;
+
+    type Input = Vec<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(parse_directions(lines))
     }
 
-    {
-        let pt2 = tetris_pt2(&dirs, 1000000000000);
-        println!("Parse 2: {}", pt2);
+    fn part1(dirs: &Self::Input) -> usize {
+        tetris_pt1(dirs, 2022)
+    }
+
+    fn part2(dirs: &Self::Input) -> usize {
+        tetris_pt2(dirs, 1000000000000)
     }
 }
 
-const STARTING_LEFT_PADDING: usize = 2;
+const STARTING_LEFT_PADDING: i32 = 2;
 // 1, 2, 3 are the padding. 0 and negatives are indexing into the actual grid.
 const STARTING_BOTTOM_PADDING: i32 = 4;
 const GRID_WIDTH: usize = 7;
@@ -42,154 +53,73 @@ const SQUARE: char = 'S';
 
 const EMPTY: char = '.';
 const FILLED: char = '#';
-const FALLING: char = '@';
+const _FALLING: char = '@';
 
 const SHAPES: [char; 5] = [HORIE, PLUS, ELL, VERTIE, SQUARE];
 
 type Row = [char; GRID_WIDTH];
 type Grid = Vec<Row>;
 
-fn tetris_pt1(directions: &Vec<char>, num_rocks: usize) -> usize {
-    let mut grid = create_grid();
-    let mut shape_index: usize = 0;
-    let mut dir_index: usize = 0;
-
-    for _i in 0..num_rocks {
-        let mut position = get_start(SHAPES[shape_index]);
-        while push_and_drop(&mut grid, &mut position, directions[dir_index]) {
-            dir_index = (dir_index + 1) % directions.len();
-        }
+// Drops one rock until it lands, giving back which jet comes next.
+fn drop_rock(
+    grid: &mut Grid,
+    directions: &Vec<char>,
+    shape_index: usize,
+    dir_index: usize,
+) -> usize {
+    let mut dir_index = dir_index;
+    let mut position = get_start(SHAPES[shape_index]);
+    while push_and_drop(grid, &mut position, directions[dir_index]) {
         dir_index = (dir_index + 1) % directions.len();
-        shape_index = (shape_index + 1) % SHAPES.len();
     }
-    return grid.len();
+    return (dir_index + 1) % directions.len();
 }
 
-type Log = (char, usize, usize);
-
-fn tetris_pt2(directions: &Vec<char>, num_rocks: usize) -> usize {
+fn tetris_pt1(directions: &Vec<char>, num_rocks: usize) -> usize {
     let mut grid = create_grid();
     let mut shape_index: usize = 0;
     let mut dir_index: usize = 0;
-    let mut rock: usize = 0;
-    let mut logs: Vec<Log> = Vec::new();
-    loop {
-        let shape = SHAPES[shape_index];
-        let mut position = get_start(shape);
-        let mut num_dirs = 0;
-        let start_height = grid.len();
-        while push_and_drop(&mut grid, &mut position, directions[dir_index]) {
-            dir_index = (dir_index + 1) % directions.len();
-            num_dirs += 1;
-        }
-        logs.push((shape, num_dirs, grid.len() - start_height));
 
-        dir_index = (dir_index + 1) % directions.len();
+    for _i in 0..num_rocks {
+        dir_index = drop_rock(&mut grid, directions, shape_index, dir_index);
         shape_index = (shape_index + 1) % SHAPES.len();
-        rock += 1;
-
-        if repeats(&logs) {
-            let mut height = grid.len();
-            let pattern = &logs[0..logs.len() / 2];
-            let pattern_rocks = pattern.iter().fold(0, |acc, (_, _, height)| acc + height);
-            let rocks_left = num_rocks - rock;
-            rock += (rocks_left / pattern_rocks) * pattern_rocks;
-            for i in 0..(rocks_left % pattern_rocks) {
-                let (_, _, gained_height) = pattern[i];
-                height += gained_height;
-                rock += 1;
-            }
-            assert!(
-                num_rocks == rock,
-                "Should have dropped {} rocks, got {}",
-                num_rocks,
-                rock
-            );
-            return height;
-        } else {
-            println!("at rock {}", rock);
-        }
-
-        if rock == num_rocks {
-            break;
-        }
     }
     return grid.len();
 }
 
-fn repeats(logs: &Vec<Log>) -> bool {
-    if logs.len() < SHAPES.len() {
-        return false;
-    }
+// The next shape and jet, and how far down from the top each column's highest
+// rock is. Rocks can't fall past those, so the same skyline with the same
+// shape and jet coming up plays out the same way.
+type Log = (usize, usize, [usize; GRID_WIDTH]);
 
-    // Must be able to be divided in half.
-    if logs.len() % 2 != 0 {
-        return false;
-    }
-
-    let midpoint = logs.len() / 2;
-    let first_half = &logs[0..midpoint];
-    let second_half = &logs[midpoint..logs.len()];
-    for i in 0..first_half.len() {
-        if first_half[i] != second_half[i] {
-            return false;
-        }
-    }
-
-    return true;
-}
-
-// To make it easier not to mess up x and y.
-#[derive(PartialEq, Debug, Copy, Clone)]
-pub struct Point {
-    left: usize,
-    bottom: i32,
-}
-
-impl Point {
-    pub fn new(left: usize, bottom: i32) -> Self {
-        Self {
-            left: left,
-            bottom: bottom,
-        }
-    }
-
-    pub fn start() -> Self {
-        Self {
-            left: STARTING_LEFT_PADDING,
-            bottom: STARTING_BOTTOM_PADDING,
-        }
-    }
-
-    fn left(&self) -> Option<Point> {
-        if self.left > 0 {
-            return Some(Point::new(self.left - 1, self.bottom));
+fn skyline(grid: &Grid) -> [usize; GRID_WIDTH] {
+    let mut depths = [grid.len(); GRID_WIDTH];
+    for (col, depth) in depths.iter_mut().enumerate() {
+        if let Some(d) = grid.iter().rev().position(|row| row[col] == FILLED) {
+            *depth = d;
         }
-        return None;
-    }
-
-    fn right(&self) -> Option<Point> {
-        if self.left < GRID_WIDTH - 1 {
-            return Some(Point::new(self.left + 1, self.bottom));
-        }
-        return None;
-    }
-
-    fn down(&self) -> Point {
-        return Point::new(self.left, self.bottom - 1);
     }
+    return depths;
 }
 
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "(l={},b={})", self.left, self.bottom)
-    }
+fn tetris_pt2(directions: &Vec<char>, num_rocks: usize) -> usize {
+    let mut grid = create_grid();
+    let mut heights = vec![grid.len()];
+    let rocks = cycle::find(
+        (0, 0, skyline(&grid)),
+        |&(shape_index, dir_index, _): &Log| {
+            let dir_index = drop_rock(&mut grid, directions, shape_index, dir_index);
+            heights.push(grid.len());
+            ((shape_index + 1) % SHAPES.len(), dir_index, skyline(&grid))
+        },
+    );
+    return rocks.extrapolate(&heights, num_rocks);
 }
 
-fn _print_grid_with_falling(grid: &Grid, falling: &Vec<Point>) {
+fn _print_grid_with_falling(grid: &Grid, falling: &Vec<Point<i32>>) {
     let mut copy = grid.clone();
     for p in falling {
-        set(&mut copy, *p, grid.len(), FALLING);
+        set(&mut copy, *p, grid.len(), _FALLING);
     }
     _print_grid(&copy);
 }
@@ -201,39 +131,41 @@ fn _print_grid(grid: &Grid) {
     }
 }
 
-fn get(grid: &Grid, padding: Point) -> char {
-    if padding.bottom > 0 {
+// `x` is the column and `y` counts up from the top of the stack, so the rows
+// above it are positive.
+fn get(grid: &Grid, padding: Point<i32>) -> char {
+    if padding.y > 0 {
         return EMPTY;
     }
 
-    if (grid.len() as i32 + padding.bottom) == 0 {
+    if (grid.len() as i32 + padding.y) == 0 {
         return FILLED;
     }
 
-    return grid[(grid.len() as i32 - 1 + padding.bottom) as usize][padding.left];
+    return grid[(grid.len() as i32 - 1 + padding.y) as usize][padding.x as usize];
 }
 
-fn set(grid: &mut Grid, padding: Point, height: usize, c: char) {
-    let y = (height as i32 + (padding.bottom - 1)) as usize;
-    if padding.bottom > 0 {
+fn set(grid: &mut Grid, padding: Point<i32>, height: usize, c: char) {
+    let y = (height as i32 + (padding.y - 1)) as usize;
+    if padding.y > 0 {
         for _i in grid.len()..=(y) {
             grid.push([EMPTY; GRID_WIDTH]);
         }
     }
 
-    grid[y][padding.left] = c;
+    grid[y][padding.x as usize] = c;
 }
 
-fn get_start(shape: char) -> Vec<Point> {
-    let start = Point::start();
+fn get_start(shape: char) -> Vec<Point<i32>> {
+    let start = Point::new(STARTING_LEFT_PADDING, STARTING_BOTTOM_PADDING);
     match shape {
         // ####
         HORIE => {
             return Vec::from([
                 start,
-                Point::new(start.left + 1, start.bottom),
-                Point::new(start.left + 2, start.bottom),
-                Point::new(start.left + 3, start.bottom),
+                start + Point::new(1, 0),
+                start + Point::new(2, 0),
+                start + Point::new(3, 0),
             ]);
         }
         //  #
@@ -241,11 +173,11 @@ fn get_start(shape: char) -> Vec<Point> {
         // S#
         PLUS => {
             return Vec::from([
-                Point::new(start.left + 1, start.bottom),
-                Point::new(start.left, start.bottom + 1),
-                Point::new(start.left + 1, start.bottom + 1),
-                Point::new(start.left + 2, start.bottom + 1),
-                Point::new(start.left + 1, start.bottom + 2),
+                start + Point::new(1, 0),
+                start + Point::new(0, 1),
+                start + Point::new(1, 1),
+                start + Point::new(2, 1),
+                start + Point::new(1, 2),
             ]);
         }
         //   #
@@ -254,10 +186,10 @@ fn get_start(shape: char) -> Vec<Point> {
         ELL => {
             return Vec::from([
                 start,
-                Point::new(start.left + 1, start.bottom),
-                Point::new(start.left + 2, start.bottom),
-                Point::new(start.left + 2, start.bottom + 1),
-                Point::new(start.left + 2, start.bottom + 2),
+                start + Point::new(1, 0),
+                start + Point::new(2, 0),
+                start + Point::new(2, 1),
+                start + Point::new(2, 2),
             ])
         }
         // #
@@ -267,9 +199,9 @@ fn get_start(shape: char) -> Vec<Point> {
         VERTIE => {
             return Vec::from([
                 start,
-                Point::new(start.left, start.bottom + 1),
-                Point::new(start.left, start.bottom + 2),
-                Point::new(start.left, start.bottom + 3),
+                start + Point::new(0, 1),
+                start + Point::new(0, 2),
+                start + Point::new(0, 3),
             ]);
         }
         // ##
@@ -277,9 +209,9 @@ fn get_start(shape: char) -> Vec<Point> {
         SQUARE => {
             return Vec::from([
                 start,
-                Point::new(start.left, start.bottom + 1),
-                Point::new(start.left + 1, start.bottom),
-                Point::new(start.left + 1, start.bottom + 1),
+                start + Point::new(0, 1),
+                start + Point::new(1, 0),
+                start + Point::new(1, 1),
             ]);
         }
         _ => assert!(false, "wrong shape: {}", shape),
@@ -288,30 +220,24 @@ fn get_start(shape: char) -> Vec<Point> {
     return Vec::new();
 }
 
-fn push_and_drop(grid: &mut Grid, position: &mut Vec<Point>, dir: char) -> bool {
+fn push_and_drop(grid: &mut Grid, position: &mut Vec<Point<i32>>, dir: char) -> bool {
     //	If any movement would cause any part of the rock to move into the walls,
     // floor, or a stopped rock, the movement instead does not occur.
+    let push = match dir {
+        GO_LEFT => Point::new(-1, 0),
+        GO_RIGHT => Point::new(1, 0),
+        _ => {
+            assert!(false, "wrong direction? {}", dir);
+            Point::new(0, 0)
+        }
+    };
     if position
         .iter()
-        .map(|p| match dir {
-            GO_LEFT => p.left(),
-            GO_RIGHT => p.right(),
-            _ => {
-                assert!(false, "wrong direction? {}", dir);
-                return None;
-            }
-        })
-        .all(|p| match p {
-            None => false,
-            Some(ps) => get(grid, ps) == EMPTY,
-        })
+        .map(|&p| p + push)
+        .all(|p| p.x >= 0 && p.x < GRID_WIDTH as i32 && get(grid, p) == EMPTY)
     {
-        for i in 0..position.len() {
-            match dir {
-                GO_LEFT => position[i] = position[i].left().expect("just checked left"),
-                GO_RIGHT => position[i] = position[i].right().expect("just checked right"),
-                _ => assert!(false, "what are you doing"),
-            }
+        for p in position.iter_mut() {
+            *p += push;
         }
     }
 
@@ -319,13 +245,10 @@ fn push_and_drop(grid: &mut Grid, position: &mut Vec<Point>, dir: char) -> bool
     // floor or an already-fallen rock, the falling rock stops where it is
     // (having landed on something).
     let grid_height = grid.len();
-    if position
-        .iter()
-        .map(|&p| p.down())
-        .all(|p| get(grid, p) == EMPTY)
-    {
-        for i in 0..position.len() {
-            position[i] = position[i].down();
+    let down = Point::new(0, -1);
+    if position.iter().all(|&p| get(grid, p + down) == EMPTY) {
+        for p in position.iter_mut() {
+            *p += down;
         }
         return true;
     }
@@ -341,8 +264,7 @@ fn create_grid() -> Grid {
     return Vec::new();
 }
 
-fn parse_directions(filename: &str) -> Vec<char> {
-    let lines: Vec<String> = read_lines(filename);
+fn parse_directions(lines: &[String]) -> Vec<char> {
     assert!(
         lines.len() == 1,
         "there should only be 1 line: got {}",
@@ -351,3 +273,5 @@ fn parse_directions(filename: &str) -> Vec<char> {
 
     return lines[0].chars().collect::<Vec<char>>();
 }
+
+advent_of_code::example_tests!(Day, "2022/day17");
@@ -1,168 +1,195 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::point::{Point, Point3};
+use advent_of_code::utils::search::bfs;
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
+use std::collections::HashSet;
 use std::fmt;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
 
-    {
-        let mut cubes = lines.iter().map(|s| Cube::parse(s)).collect::<Vec<Cube>>();
-        let exposed = count_exposed(&mut cubes);
-        println!("Part 1: {}", exposed);
-    }
-}
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 18;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Cube {
-    x: i32,
-    y: i32,
-    z: i32,
-    exposed: [bool; 6],
-}
+    type Input = Vec<Cube>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-const LEFT: usize = 0;
-const RIGHT: usize = 1;
-const TOP: usize = 2;
-const BOTTOM: usize = 3;
-const FRONT: usize = 4;
-const BACK: usize = 5;
-
-impl Cube {
-    pub fn new(x: i32, y: i32, z: i32) -> Self {
-        Self {
-            x: x,
-            y: y,
-            z: z,
-            exposed: [true; 6],
-        }
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, s)| parse_cube(i + 1, s))
+            .collect()
     }
 
-    pub fn parse(s: &str) -> Self {
-        let parts = s.split(",").collect::<Vec<&str>>();
-        Self::new(s2i(parts[0]), s2i(parts[1]), s2i(parts[2]))
+    fn part1(cubes: &Self::Input) -> usize {
+        count_exposed(cubes)
     }
 
-    fn check(&mut self, other: &mut Cube) {
-        let xd = (self.x - other.x).abs();
-        let yd = (self.y - other.y).abs();
-        let zd = (self.z - other.z).abs();
-        if xd <= 1 && yd <= 1 && zd <= 1 && (xd == 0 || yd == 0 || zd == 0) {
-            match (xd == 0, yd == 0, zd == 0) {
-                (true, true, false) => {
-                    if self.z > other.z {
-                        self.exposed[BOTTOM] = false;
-                        other.exposed[TOP] = false;
-                    } else {
-                        self.exposed[TOP] = false;
-                        other.exposed[BOTTOM] = false;
-                    }
-                }
-                (true, false, true) => {
-                    if self.y > other.y {
-                        self.exposed[BACK] = false;
-                        other.exposed[FRONT] = false;
-                    } else {
-                        self.exposed[FRONT] = false;
-                        other.exposed[BACK] = false;
-                    }
-                }
-                (false, true, true) => {
-                    if self.x > other.x {
-                        self.exposed[LEFT] = false;
-                        other.exposed[RIGHT] = false;
-                    } else {
-                        self.exposed[RIGHT] = false;
-                        other.exposed[LEFT] = false;
-                    }
-                }
-                _ => (),
+    fn part2(cubes: &Self::Input) -> usize {
+        surface(cubes).exterior
+    }
+
+    /// A slice through the droplet at each height, bottom to top.
+    fn visualize(cubes: &Self::Input, viz: &mut Visualizer) {
+        if let Some(air) = Air::around(cubes) {
+            for z in air.min.z..=air.max.z {
+                viz.frame(&air.draw_slice(z));
             }
         }
     }
 
-    fn exposed_faces(&self) -> usize {
-        return self
-            .exposed
-            .iter()
-            .fold(0, |acc, &x| if x { acc + 1 } else { acc });
+    /// How much air is trapped inside the droplet.
+    fn diagnostics(cubes: &Self::Input) -> Vec<String> {
+        vec![surface(cubes).to_string()]
     }
 }
 
-impl fmt::Display for Cube {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({},{},{})", self.x, self.y, self.z)
+pub type Cube = Point3<i32>;
+
+fn parse_cube(line: usize, s: &str) -> Result<Cube, AocError> {
+    let parts = s
+        .split(",")
+        .map(|x| parse_at::<i32>(line, x))
+        .collect::<Result<Vec<i32>, AocError>>()?;
+    if parts.len() != 3 {
+        return Err(AocError::parse(line, format!("expected x,y,z: {:?}", s)));
     }
+    Ok(Point3::new(parts[0], parts[1], parts[2]))
 }
 
-fn count_exposed(cubes: &mut Vec<Cube>) -> usize {
-    let mut xps: Vec<usize> = cubes
+// A face is exposed when the cube it leads to isn't part of the droplet.
+fn count_exposed(cubes: &[Cube]) -> usize {
+    let droplet = cubes.iter().copied().collect::<HashSet<Cube>>();
+    return cubes
         .iter()
-        .enumerate()
-        .map(|(i, _)| i)
-        .collect::<Vec<usize>>();
-    let mut yps = xps.clone();
-    let mut zps = xps.clone();
-
-    xps.sort_by(|&a, &b| cubes[a].x.cmp(&cubes[b].x));
-    yps.sort_by(|&a, &b| cubes[a].y.cmp(&cubes[b].y));
-    zps.sort_by(|&a, &b| cubes[a].z.cmp(&cubes[b].z));
-
-    for a in 0..xps.len() {
-        for b in a + 1..xps.len() {
-            if (cubes[xps[a]].x - cubes[xps[b]].x).abs() > 1 {
-                break;
-            }
+        .flat_map(|c| c.neighbors6())
+        .filter(|n| !droplet.contains(n))
+        .count();
+}
 
-            let mut a_cube = cubes[xps[a]];
-            let mut b_cube = cubes[xps[b]];
-            a_cube.check(&mut b_cube);
-            cubes[xps[a]] = a_cube;
-            cubes[xps[b]] = b_cube;
-        }
+/// How the droplet's faces are split between the air outside it and air
+/// trapped inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Surface {
+    /// Faces touching any air at all, the same as `count_exposed`.
+    total: usize,
+    /// Faces touching the air outside.
+    exterior: usize,
+    /// How many separate pockets of air are trapped inside.
+    pockets: usize,
+    /// How many cubes of air the pockets hold between them.
+    pocket_volume: usize,
+}
+
+impl fmt::Display for Surface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "exterior faces: {} of {}, air pockets: {}, pocket volume: {}",
+            self.exterior, self.total, self.pockets, self.pocket_volume
+        )
     }
+}
 
-    for a in 0..yps.len() {
-        for b in a + 1..yps.len() {
-            if (cubes[yps[a]].y - cubes[yps[b]].y).abs() > 1 {
-                break;
-            }
+/// The air in a box round the droplet. The box is padded by one on every
+/// side, so the air outside is all joined up round the droplet and one flood
+/// fill from a corner finds all of it.
+struct Air {
+    droplet: HashSet<Cube>,
+    min: Cube,
+    max: Cube,
+    outside: HashSet<Cube>,
+    trapped: HashSet<Cube>,
+    /// How many separate pockets `trapped` is split into.
+    pockets: usize,
+}
 
-            let mut a_cube = cubes[yps[a]];
-            let mut b_cube = cubes[yps[b]];
-            a_cube.check(&mut b_cube);
-            cubes[yps[a]] = a_cube;
-            cubes[yps[b]] = b_cube;
-        }
-    }
+impl Air {
+    /// `None` if there's no droplet to put a box round.
+    fn around(cubes: &[Cube]) -> Option<Self> {
+        let first = *cubes.first()?;
+        let droplet = cubes.iter().copied().collect::<HashSet<Cube>>();
+        let min = cubes.iter().fold(first, |m, c| {
+            Point3::new(m.x.min(c.x), m.y.min(c.y), m.z.min(c.z))
+        }) - Point3::new(1, 1, 1);
+        let max = cubes.iter().fold(first, |m, c| {
+            Point3::new(m.x.max(c.x), m.y.max(c.y), m.z.max(c.z))
+        }) + Point3::new(1, 1, 1);
+        let in_box = |c: &Cube| {
+            (min.x..=max.x).contains(&c.x)
+                && (min.y..=max.y).contains(&c.y)
+                && (min.z..=max.z).contains(&c.z)
+        };
+        let air = |c: &Cube| {
+            c.neighbors6()
+                .into_iter()
+                .filter(|n| in_box(n) && !droplet.contains(n))
+                .collect::<Vec<Cube>>()
+        };
+        let outside = bfs([min], air).dist.into_keys().collect::<HashSet<Cube>>();
 
-    for a in 0..zps.len() {
-        for b in a + 1..zps.len() {
-            if (cubes[zps[a]].z - cubes[zps[b]].z).abs() > 1 {
-                break;
+        let mut pockets = 0;
+        let mut trapped: HashSet<Cube> = HashSet::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let c = Point3::new(x, y, z);
+                    if droplet.contains(&c) || outside.contains(&c) || trapped.contains(&c) {
+                        continue;
+                    }
+                    pockets += 1;
+                    trapped.extend(bfs([c], air).dist.into_keys());
+                }
             }
-
-            let mut a_cube = cubes[zps[a]];
-            let mut b_cube = cubes[zps[b]];
-            a_cube.check(&mut b_cube);
-            cubes[zps[a]] = a_cube;
-            cubes[zps[b]] = b_cube;
         }
+
+        return Some(Self {
+            droplet,
+            min,
+            max,
+            outside,
+            trapped,
+            pockets,
+        });
     }
 
-    return cubes.iter().map(|&c| c.exposed_faces()).sum();
+    /// The box at height `z`, with lava as `#` and trapped air as `o`.
+    fn draw_slice(&self, z: i32) -> Frame {
+        let corner = |c: Cube| Point::new(c.x as i64, c.y as i64);
+        return viz::draw(corner(self.min), corner(self.max), |p| {
+            let c = Point3::new(p.x as i32, p.y as i32, z);
+            if self.droplet.contains(&c) {
+                return Cell::colored('#', Rgb(220, 90, 40));
+            }
+            if self.trapped.contains(&c) {
+                return Cell::colored('o', Rgb(90, 150, 230));
+            }
+            return Cell::from('.');
+        });
+    }
 }
 
-fn s2i(s: &str) -> i32 {
-    return s.parse::<i32>().unwrap();
+fn surface(cubes: &[Cube]) -> Surface {
+    let Some(air) = Air::around(cubes) else {
+        return Surface::default();
+    };
+    let faces = cubes.iter().flat_map(|c| c.neighbors6());
+    return Surface {
+        total: count_exposed(cubes),
+        exterior: faces.filter(|n| air.outside.contains(n)).count(),
+        pockets: air.pockets,
+        pocket_volume: air.trapped.len(),
+    };
 }
 
 #[cfg(test)]
@@ -171,18 +198,100 @@ mod tests {
 
     #[test]
     fn example_works() {
-        let mut input = Vec::from([
+        let input = Vec::from([
             "2,2,2", "1,2,2", "3,2,2", "2,1,2", "2,3,2", "2,2,1", "2,2,3", "2,2,4", "2,2,6",
             "1,2,5", "3,2,5", "2,1,5", "2,3,5",
         ])
         .iter()
-        .map(|&x| Cube::parse(x))
+        .enumerate()
+        .map(|(i, &x)| parse_cube(i + 1, x).unwrap())
         .collect::<Vec<Cube>>();
 
-        {
-            let exposed = count_exposed(&mut input);
-            println!("{:?}", input);
-            assert_eq!(64, exposed, "Count exposed");
+        assert_eq!(64, count_exposed(&input), "Count exposed");
+        assert_eq!(
+            surface(&input),
+            Surface {
+                total: 64,
+                exterior: 58,
+                pockets: 1,
+                pocket_volume: 1,
+            }
+        );
+        assert_eq!(
+            Day::diagnostics(&input),
+            ["exterior faces: 58 of 64, air pockets: 1, pocket volume: 1"]
+        );
+
+        // The trapped cube of air is in the middle of the slice at z = 5.
+        let slice = Air::around(&input).unwrap().draw_slice(5);
+        let rows = (0..slice.height())
+            .map(|y| slice.row(y).iter().map(|cell| cell.ch).collect::<String>())
+            .collect::<Vec<String>>();
+        assert_eq!(rows, [".....", "..#..", ".#o#.", "..#..", "....."]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(surface(&[]), Surface::default());
+        assert!(Air::around(&[]).is_none());
+    }
+
+    /// The outside of a cube `size` long on each side with its corner at
+    /// `corner`.
+    fn shell(corner: Cube, size: i32) -> Vec<Cube> {
+        let mut cubes = Vec::new();
+        for x in 0..size {
+            for y in 0..size {
+                for z in 0..size {
+                    let on_edge = [x, y, z].iter().any(|&v| v == 0 || v == size - 1);
+                    if on_edge {
+                        cubes.push(corner + Point3::new(x, y, z));
+                    }
+                }
+            }
+        }
+        return cubes;
+    }
+
+    #[test]
+    fn test_surface() {
+        let tests = [
+            (shell(Point3::new(0, 0, 0), 3), 54, 1, 1),
+            (shell(Point3::new(-2, 0, 7), 5), 150, 1, 27),
+            (
+                [
+                    shell(Point3::new(0, 0, 0), 3),
+                    shell(Point3::new(10, 0, 0), 4),
+                ]
+                .concat(),
+                54 + 96,
+                2,
+                1 + 8,
+            ),
+            // Solid, so nothing's trapped.
+            (vec![Point3::new(1, 1, 1), Point3::new(1, 1, 2)], 10, 0, 0),
+        ];
+        for (cubes, exterior, pockets, pocket_volume) in tests {
+            let got = surface(&cubes);
+            assert_eq!(
+                (got.exterior, got.pockets, got.pocket_volume),
+                (exterior, pockets, pocket_volume),
+                "{:?}",
+                got
+            );
+            assert_eq!(got.total, count_exposed(&cubes));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [("1,2", 4), ("1,2,x", 4), ("1,2,3,4", 4)];
+        for (bad, want) in tests {
+            let lines = ["1,1,1", "2,2,2", "3,3,3", bad].map(String::from);
+            match Day::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{}", bad),
+                _ => panic!("{} should fail to parse", bad),
+            }
         }
     }
 }
@@ -1,57 +1,63 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::len_zero,
+    clippy::needless_return,
+    clippy::ptr_arg,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 19;
+
+    type Input = Vec<Blueprint>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Blueprint::parse(i + 1, line))
+            .collect()
+    }
 
-    {
-        part_1(&lines);
-        part_2(&lines);
+    fn part1(blueprints: &Self::Input) -> usize {
+        part_1(blueprints)
+    }
+
+    fn part2(blueprints: &Self::Input) -> usize {
+        part_2(blueprints)
     }
 }
 
-fn part_1(lines: &Vec<String>) {
-    let qualities = lines
+fn part_1(blueprints: &Vec<Blueprint>) -> usize {
+    let qualities = blueprints
         .iter()
-        .map(|line| Blueprint::parse(line))
-        .map(|bp| {
-            let max_geodes = bp.max_geodes(24);
-            println!("{:?} has max {} = {}", bp, max_geodes, bp.id * max_geodes);
-            bp.id * max_geodes
-        })
+        .map(|bp| bp.id * bp.max_geodes(24))
         .sum::<usize>();
 
-    // 1936 is too low.
-    // 2068 is too low.
-    // 2160
-    println!("Part 1: {:?}", qualities);
+    return qualities;
 }
 
-fn part_2(lines: &Vec<String>) {
-    let qualities = lines[0..3]
+fn part_2(blueprints: &Vec<Blueprint>) -> usize {
+    let qualities = blueprints[0..3]
         .iter()
-        .map(|line| Blueprint::parse(line))
-        .map(|bp| {
-            let max_geodes = bp.max_geodes(32);
-            println!("{:?} has max {} = {}", bp, max_geodes, bp.id * max_geodes);
-            max_geodes
-        })
+        .map(|bp| bp.max_geodes(32))
         .reduce(|acc, x| acc * x)
         .unwrap();
 
-    // 80040 is too high
-    // 13340
-    println!("Part 2: {:?}", qualities)
+    return qualities;
 }
 
 type Rock = usize;
@@ -93,12 +99,8 @@ impl State {
     }
 
     fn prev(&self) -> Option<State> {
-        let mut state = self.clone();
-        if ROCKS
-            .iter()
-            .map(|&r| state.rocks[r] >= state.robots[r])
-            .all(|b| b)
-        {
+        let mut state = *self;
+        if ROCKS.iter().all(|&r| state.rocks[r] >= state.robots[r]) {
             state.rocks[ORE] -= state.robots[ORE];
             state.rocks[CLAY] -= state.robots[CLAY];
             state.rocks[OBSIDIAN] -= state.robots[OBSIDIAN];
@@ -138,7 +140,7 @@ impl State {
     }
 
     fn buy(&self, r: Rock, bp: &Blueprint) -> Option<State> {
-        let mut state = self.clone();
+        let mut state = *self;
         if !state.update() {
             return None;
         }
@@ -202,39 +204,22 @@ pub struct ClayCost {
 }
 
 impl Blueprint {
-    pub fn parse(s: &str) -> Self {
-        let blueprint_re= Regex::new(r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").unwrap();
-        let mut id: usize = 0;
-        let mut ore_cost = 0;
-        let mut clay_cost = 0;
-        let mut obsidian_cost_ore = 0;
-        let mut obsidian_cost_clay = 0;
-        let mut geode_cost_ore = 0;
-        let mut geode_cost_obsidian = 0;
-
-        assert!(blueprint_re.is_match(s));
-        for m in blueprint_re.captures_iter(s) {
-            for (i, capt) in m.iter().enumerate() {
-                if let Some(sub) = capt {
-                    if i == 0 {
-                        continue;
-                    }
-
-                    let v = str2usize(sub.as_str());
-                    match i {
-                        1 => id = v,
-                        2 => ore_cost = v,
-                        3 => clay_cost = v,
-                        4 => obsidian_cost_ore = v,
-                        5 => obsidian_cost_clay = v,
-                        6 => geode_cost_ore = v,
-                        7 => geode_cost_obsidian = v,
-                        _ => (),
-                    }
-                }
-            }
-        }
-        return Self {
+    pub fn parse(line_no: usize, s: &str) -> Result<Self, AocError> {
+        let (
+            id,
+            ore_cost,
+            clay_cost,
+            obsidian_cost_ore,
+            obsidian_cost_clay,
+            geode_cost_ore,
+            geode_cost_obsidian,
+        ) = scan!(
+            line_no,
+            s,
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian."
+                => usize, usize, usize, usize, usize, usize, usize
+        )?;
+        return Ok(Self {
             id: id,
             ore_cost: OreCost { ores: ore_cost },
             clay_cost: ClayCost { ores: clay_cost },
@@ -246,7 +231,7 @@ impl Blueprint {
                 ores: geode_cost_ore,
                 obsidians: geode_cost_obsidian,
             },
-        };
+        });
     }
 
     fn lowest_ore_cost(&self) -> usize {
@@ -295,9 +280,7 @@ impl Blueprint {
                 .iter()
                 .enumerate()
                 .filter(|&(_, should_buy)| *should_buy)
-                .map(|(r, _)| curr.buy(r, self))
-                .filter(|&s| s.is_some())
-                .map(|s| s.unwrap())
+                .filter_map(|(r, _)| curr.buy(r, self))
                 .rev()
                 .collect::<Vec<State>>();
             states.extend(bought.iter());
@@ -329,10 +312,6 @@ impl Blueprint {
     }
 }
 
-fn str2usize(s: &str) -> usize {
-    return s.parse::<usize>().unwrap();
-}
-
 //impl fmt::Display for Blueprint {
 //    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 //        write!(f, "({},{},{})", self.x, self.y, self.z)
@@ -354,7 +333,7 @@ mod tests {
 ]);
 
         for (input, want) in tests {
-            let bp = Blueprint::parse(input);
+            let bp = Blueprint::parse(1, input).unwrap();
             let got = bp.max_geodes(24);
             println!("BP {:?} = {}", bp, got);
             assert_eq!(want, got, "\n{:?}\nWant {}, but got {}", bp, want, got);
@@ -1,27 +1,36 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_range_loop, clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 20;
 
-    {
-        let mut file = parse_lines(&lines, 1);
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> i64 {
+        let mut file = parse_lines(lines, 1);
         decrypt(&mut file, 1);
-        println!("Part 1: {}", get_score(&file));
+        get_score(&file)
     }
-    {
-        let mut file = parse_lines(&lines, 811589153);
+
+    fn part2(lines: &Self::Input) -> i64 {
+        let mut file = parse_lines(lines, 811589153);
         decrypt(&mut file, 10);
-        println!("Part 2: {}", get_score(&file));
+        get_score(&file)
     }
 }
 
@@ -120,9 +129,9 @@ mod tests {
         ];
         for (f, want_file, want) in tests {
             let mut file: Vec<i64> = f.clone();
-            decrypt(&mut file);
+            decrypt(&mut file, 1);
             _circular_eq(&file, &want_file);
-            assert_eq!(pt1(&file), want);
+            assert_eq!(get_score(&file), want);
         }
     }
 }
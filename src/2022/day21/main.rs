@@ -1,29 +1,47 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::len_zero,
+    clippy::needless_return
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
 
-    {
-        let (mut known, mut unknown) = parse_input_pt1(&lines);
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 21;
+
+    /// Each monkey's name and job.
+    type Input = Vec<(String, Job)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_job(i + 1, line))
+            .collect()
+    }
+
+    fn part1(jobs: &Self::Input) -> i64 {
+        let (mut known, mut unknown) = parse_input_pt1(jobs);
         solve_pt1(&mut known, &mut unknown);
-        println!("Part 1: {}", known[&String::from("root")]);
+        known[&String::from("root")]
     }
-    {
-        let (mut known, mut unknown) = parse_input_pt2(&lines);
+
+    fn part2(jobs: &Self::Input) -> i64 {
+        let (mut known, mut unknown) = parse_input_pt2(jobs);
         solve_pt2(&mut known, &mut unknown);
-        println!("Part 2: {}", known[&String::from("humn")]);
+        known[&String::from("humn")]
     }
 }
 
@@ -35,54 +53,39 @@ pub struct Operation {
     is_val: bool,
 }
 
-fn parse_input_pt1(lines: &Vec<String>) -> (HashMap<String, i64>, HashMap<String, Operation>) {
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Job {
+    Number(i64),
+    Operation(String, char, String),
+}
+
+fn parse_job(line_no: usize, line: &str) -> Result<(String, Job), AocError> {
+    if let Ok((name, tok1, op, tok2)) =
+        scan!(line_no, line, "{}: {} {} {}" => String, String, char, String)
+    {
+        return Ok((name, Job::Operation(tok1, op, tok2)));
+    }
+    let (name, val) = scan!(line_no, line, "{}: {}" => String, i64)?;
+    Ok((name, Job::Number(val)))
+}
+
+fn parse_input_pt1(jobs: &[(String, Job)]) -> (HashMap<String, i64>, HashMap<String, Operation>) {
     let mut known_values: HashMap<String, i64> = HashMap::new();
     let mut unknown_values: HashMap<String, Operation> = HashMap::new();
 
-    let number_re = Regex::new(r"(\w+): (\d+)").unwrap();
-    let operation_re = Regex::new(r"(\w+): (\w+) (.) (\w+)").unwrap();
-
-    for line in lines {
-        let mut name = String::from("");
-        if number_re.is_match(&line) {
-            let mut val: i64 = 0;
-            for m in number_re.captures_iter(&line) {
-                for (i, capt) in m.iter().enumerate() {
-                    if let Some(sub) = capt {
-                        match i {
-                            1 => name = String::from(sub.as_str()),
-                            2 => val = sub.as_str().parse::<i64>().unwrap(),
-                            _ => (),
-                        }
-                    }
-                }
+    for (name, job) in jobs {
+        match job {
+            Job::Number(val) => {
+                known_values.entry(name.clone()).or_insert(*val);
             }
-            known_values.entry(name).or_insert(val);
-        } else if operation_re.is_match(&line) {
-            let mut tok1 = String::from("");
-            let mut tok2 = String::from("");
-            let mut op = ' ';
-            for m in operation_re.captures_iter(&line) {
-                for (i, capt) in m.iter().enumerate() {
-                    if let Some(sub) = capt {
-                        match i {
-                            1 => name = String::from(sub.as_str()),
-                            2 => tok1 = String::from(sub.as_str()),
-                            3 => op = sub.as_str().chars().collect::<Vec<char>>()[0],
-                            4 => tok2 = String::from(sub.as_str()),
-                            _ => (),
-                        }
-                    }
-                }
+            Job::Operation(tok1, op, tok2) => {
+                unknown_values.entry(name.clone()).or_insert(Operation {
+                    tok1: tok1.clone(),
+                    op: *op,
+                    tok2: tok2.clone(),
+                    is_val: false,
+                });
             }
-            unknown_values.entry(name).or_insert(Operation {
-                tok1: tok1,
-                op: op,
-                tok2: tok2,
-                is_val: false,
-            });
-        } else {
-            assert!(false, "Line did not match: {}", line);
         }
     }
 
@@ -115,68 +118,29 @@ fn solve_pt1(known: &mut HashMap<String, i64>, unknown: &mut HashMap<String, Ope
     }
 }
 
-fn parse_input_pt2(lines: &Vec<String>) -> (HashMap<String, i64>, HashMap<String, Operation>) {
+fn parse_input_pt2(jobs: &[(String, Job)]) -> (HashMap<String, i64>, HashMap<String, Operation>) {
     let mut known_values: HashMap<String, i64> = HashMap::new();
     let mut unknown_values: HashMap<String, Operation> = HashMap::new();
 
-    let number_re = Regex::new(r"(\w+): (\d+)").unwrap();
-    let operation_re = Regex::new(r"(\w+): (\w+) (.) (\w+)").unwrap();
-
-    for line in lines {
-        let mut name = String::from("");
-        if number_re.is_match(&line) {
-            let mut val: i64 = 0;
-            for m in number_re.captures_iter(&line) {
-                for (i, capt) in m.iter().enumerate() {
-                    if let Some(sub) = capt {
-                        match i {
-                            1 => name = String::from(sub.as_str()),
-                            2 => val = sub.as_str().parse::<i64>().unwrap(),
-                            _ => (),
-                        }
-                    }
-                }
-            }
-            match name.as_str() {
-                "humn" => {
-                    unknown_values.entry(name).or_insert(Operation {
-                        is_val: true,
-                        tok1: String::from(""),
-                        tok2: String::from(""),
-                        op: ' ',
-                    });
-                    ()
-                }
-                _ => *known_values.entry(name).or_insert(val) = val,
+    for (name, job) in jobs {
+        match job {
+            Job::Number(_) if name == "humn" => {
+                unknown_values.entry(name.clone()).or_insert(Operation {
+                    is_val: true,
+                    tok1: String::from(""),
+                    tok2: String::from(""),
+                    op: ' ',
+                });
             }
-        } else if operation_re.is_match(&line) {
-            let mut tok1 = String::from("");
-            let mut tok2 = String::from("");
-            let mut op = ' ';
-            for m in operation_re.captures_iter(&line) {
-                for (i, capt) in m.iter().enumerate() {
-                    if let Some(sub) = capt {
-                        match i {
-                            1 => name = String::from(sub.as_str()),
-                            2 => tok1 = String::from(sub.as_str()),
-                            3 => match name.as_str() {
-                                "root" => op = '=',
-                                _ => op = sub.as_str().chars().collect::<Vec<char>>()[0],
-                            },
-                            4 => tok2 = String::from(sub.as_str()),
-                            _ => (),
-                        }
-                    }
-                }
+            Job::Number(val) => *known_values.entry(name.clone()).or_insert(*val) = *val,
+            Job::Operation(tok1, op, tok2) => {
+                unknown_values.entry(name.clone()).or_insert(Operation {
+                    tok1: tok1.clone(),
+                    op: if name == "root" { '=' } else { *op },
+                    tok2: tok2.clone(),
+                    is_val: false,
+                });
             }
-            unknown_values.entry(name).or_insert(Operation {
-                tok1: tok1,
-                op: op,
-                tok2: tok2,
-                is_val: false,
-            });
-        } else {
-            assert!(false, "Line did not match: {}", line);
         }
     }
 
@@ -308,6 +272,7 @@ mod tests {
         .iter()
         .map(|&x| String::from(x))
         .collect::<Vec<String>>();
+        let input = Day::parse(&input).unwrap();
 
         {
             let (mut known, mut unknown) = parse_input_pt1(&input);
@@ -1,24 +1,40 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::len_zero,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::ptr_arg,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::point::{Direction, Point, Point3};
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    {
-        let exposed = part_1(&lines);
-        // 57232 is too low.
-        // 58236 is too low.
-        // 64256
-        println!("Part 1: {}", exposed);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 22;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        part_1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        part_2(lines)
     }
 }
 
@@ -64,17 +80,36 @@ fn part_1(lines: &Vec<String>) -> usize {
         state.update(inst, &rows, &cols);
     }
 
+    return password(&state);
+}
+
+fn part_2(lines: &Vec<String>) -> usize {
+    let map_end = lines.iter().position(|l| l.len() == 0).unwrap();
+    let map = lines[..map_end]
+        .iter()
+        .map(|l| l.as_str())
+        .collect::<Vec<&str>>();
+    let cube = Cube::fold(&map);
+
+    let start = Point::new(map[0].chars().position(|c| c == OK).unwrap(), 0);
+    let mut state = State::new(start);
+    let instructions = Instruction::parse(lines[lines.len() - 1].as_str());
+
+    for &inst in &instructions {
+        state.update_on_cube(inst, &cube);
+    }
+
+    return password(&state);
+}
+
+fn password(state: &State) -> usize {
     return (state.loc.y + 1) * 1000
         + (state.loc.x + 1) * 4
-        + match state.dir.dir {
-            RIGHT => 0,
-            DOWN => 1,
-            LEFT => 2,
-            UP => 3,
-            _ => {
-                assert!(false);
-                4
-            }
+        + match state.dir {
+            Direction::East => 0,
+            Direction::South => 1,
+            Direction::West => 2,
+            Direction::North => 3,
         };
 }
 
@@ -84,8 +119,6 @@ const WRAP: char = ' ';
 
 const LEFT: char = 'L';
 const RIGHT: char = 'R';
-const UP: char = 'U';
-const DOWN: char = 'D';
 
 #[derive(Debug, Clone)]
 pub struct Slice {
@@ -115,7 +148,7 @@ impl Slice {
             }
         }
         Self {
-            offset: (&v).iter().position(|&c| c != WRAP).unwrap(),
+            offset: v.iter().position(|&c| c != WRAP).unwrap(),
             elems: elems,
         }
     }
@@ -161,52 +194,43 @@ impl Instruction {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Direction {
-    dir: char,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct State {
-    loc: Point,
+    loc: Point<usize>,
     dir: Direction,
 }
 
 impl State {
-    pub fn new(p: Point) -> Self {
+    pub fn new(p: Point<usize>) -> Self {
         return Self {
             loc: p,
-            dir: Direction::new(),
+            dir: Direction::East,
         };
     }
 
+    fn turn(&mut self, c: char) {
+        self.dir = match c {
+            LEFT => self.dir.turn_left(),
+            _ => self.dir.turn_right(),
+        }
+    }
+
     fn update(&mut self, instruction: Instruction, rows: &Vec<Slice>, cols: &Vec<Slice>) {
         match (instruction.turn, instruction.walk) {
-            (Some(c), None) => self.dir.turn(c),
+            (Some(c), None) => self.turn(c),
             (None, Some(w)) => {
                 let slice: &Slice;
                 let mut curr: usize;
-                let positive = self.dir.dir == DOWN || self.dir.dir == RIGHT;
-                match self.dir.dir {
-                    UP | DOWN => {
+                let positive = self.dir == Direction::South || self.dir == Direction::East;
+                match self.dir {
+                    Direction::North | Direction::South => {
                         slice = &cols[self.loc.x];
                         curr = self.loc.y - slice.offset;
                     }
-                    LEFT | RIGHT => {
+                    Direction::West | Direction::East => {
                         slice = &rows[self.loc.y];
                         curr = self.loc.x - slice.offset;
                     }
-                    _ => {
-                        assert!(false);
-                        slice = &rows[self.loc.y];
-                        curr = 0;
-                    }
                 }
 
                 for _ in 0..w {
@@ -230,75 +254,310 @@ impl State {
                     }
                 }
 
-                match self.dir.dir {
-                    UP | DOWN => self.loc = Point::new(self.loc.x, curr + slice.offset),
-                    LEFT | RIGHT => self.loc = Point::new(curr + slice.offset, self.loc.y),
-                    _ => assert!(false),
+                match self.dir {
+                    Direction::North | Direction::South => {
+                        self.loc = Point::new(self.loc.x, curr + slice.offset)
+                    }
+                    Direction::West | Direction::East => {
+                        self.loc = Point::new(curr + slice.offset, self.loc.y)
+                    }
                 }
             }
             _ => assert!(false),
         }
     }
+
+    /// The same as `update`, but walking off an edge of the map carries on
+    /// over the fold onto whichever face of the cube is there.
+    fn update_on_cube(&mut self, instruction: Instruction, cube: &Cube) {
+        match (instruction.turn, instruction.walk) {
+            (Some(c), None) => self.turn(c),
+            (None, Some(w)) => {
+                for _ in 0..w {
+                    let (next, dir) = cube.step(self.loc, self.dir);
+                    if cube.at(next) == WALL {
+                        break;
+                    }
+                    self.loc = next;
+                    self.dir = dir;
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+}
+
+/// One face of the folded cube: where its top left corner is on the map, and
+/// which way its outward normal, east and south point in 3D.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Face {
+    corner: Point<usize>,
+    normal: Point3<i64>,
+    east: Point3<i64>,
+    south: Point3<i64>,
 }
 
-impl Direction {
-    pub fn new() -> Self {
-        return Self { dir: RIGHT };
+impl Face {
+    /// The face next to this one on the net in direction `dir`, once the net
+    /// is folded up along their shared edge.
+    fn neighbor(&self, dir: Direction, corner: Point<usize>) -> Face {
+        let (normal, east, south) = match dir {
+            Direction::East => (self.east, -self.normal, self.south),
+            Direction::West => (-self.east, self.normal, self.south),
+            Direction::South => (self.south, self.east, -self.normal),
+            Direction::North => (-self.south, self.east, self.normal),
+        };
+        Face {
+            corner,
+            normal,
+            east,
+            south,
+        }
+    }
+
+    /// Which way going `dir` on this face heads in 3D.
+    fn heading(&self, dir: Direction) -> Point3<i64> {
+        match dir {
+            Direction::East => self.east,
+            Direction::West => -self.east,
+            Direction::South => self.south,
+            Direction::North => -self.south,
+        }
     }
-    fn turn(&mut self, dir: char) {
-        self.dir = match dir {
-            RIGHT => match self.dir {
-                LEFT => UP,
-                UP => RIGHT,
-                RIGHT => DOWN,
-                DOWN => LEFT,
-                _ => {
-                    assert!(false);
-                    LEFT
+}
+
+fn dot(a: Point3<i64>, b: Point3<i64>) -> i64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+/// The map folded up into a cube. Any of the 11 ways of drawing a cube's net
+/// works: the faces are found by walking the net from the first one, folding
+/// each onto the cube as it's reached.
+pub struct Cube {
+    map: Vec<Vec<char>>,
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    pub fn fold(map: &[&str]) -> Self {
+        let map = map
+            .iter()
+            .map(|l| l.chars().collect::<Vec<char>>())
+            .collect::<Vec<Vec<char>>>();
+        let tiles = map.iter().flatten().filter(|&&c| c != WRAP).count();
+        let size = (1..).find(|n| 6 * n * n >= tiles).unwrap();
+        assert!(6 * size * size == tiles, "the map isn't 6 square faces");
+
+        let on_map = |corner: Point<usize>| {
+            map.get(corner.y)
+                .and_then(|row| row.get(corner.x))
+                .is_some_and(|&c| c != WRAP)
+        };
+        let first = Face {
+            corner: Point::new(map[0].iter().position(|&c| c != WRAP).unwrap(), 0),
+            normal: Point3::new(0, 0, -1),
+            east: Point3::new(1, 0, 0),
+            south: Point3::new(0, 1, 0),
+        };
+        let mut faces = vec![first];
+        let mut i = 0;
+        while i < faces.len() {
+            let face = faces[i];
+            i += 1;
+            for dir in Direction::ALL {
+                let d = dir.delta::<i64>() * size as i64;
+                let (x, y) = (face.corner.x as i64 + d.x, face.corner.y as i64 + d.y);
+                if x < 0 || y < 0 {
+                    continue;
                 }
-            },
-            LEFT => match self.dir {
-                LEFT => DOWN,
-                UP => LEFT,
-                RIGHT => UP,
-                DOWN => RIGHT,
-                _ => {
-                    assert!(false);
-                    LEFT
+                let corner = Point::new(x as usize, y as usize);
+                if on_map(corner) && faces.iter().all(|f| f.corner != corner) {
+                    faces.push(face.neighbor(dir, corner));
                 }
-            },
-            _ => {
-                assert!(false);
-                LEFT
             }
         }
+        assert!(faces.len() == 6, "the faces aren't all joined up");
+        for (i, a) in faces.iter().enumerate() {
+            assert!(
+                faces[i + 1..].iter().all(|b| b.normal != a.normal),
+                "two faces fold onto the same side of the cube"
+            );
+        }
+
+        Self { map, size, faces }
+    }
+
+    fn at(&self, p: Point<usize>) -> char {
+        self.map[p.y][p.x]
+    }
+
+    fn face_at(&self, p: Point<usize>) -> &Face {
+        self.faces
+            .iter()
+            .find(|f| {
+                (f.corner.x..f.corner.x + self.size).contains(&p.x)
+                    && (f.corner.y..f.corner.y + self.size).contains(&p.y)
+            })
+            .unwrap()
     }
-}
 
-impl Point {
-    pub fn new(x: usize, y: usize) -> Self {
-        Self { x: x, y: y }
+    /// Where one step from `p` going `dir` ends up, and which way it's facing
+    /// by then.
+    fn step(&self, p: Point<usize>, dir: Direction) -> (Point<usize>, Direction) {
+        let face = self.face_at(p);
+        let n = self.size as i64;
+        let (i, j) = ((p.x - face.corner.x) as i64, (p.y - face.corner.y) as i64);
+        let d = dir.delta::<i64>();
+        if (0..n).contains(&(i + d.x)) && (0..n).contains(&(j + d.y)) {
+            return (
+                Point::new((p.x as i64 + d.x) as usize, (p.y as i64 + d.y) as usize),
+                dir,
+            );
+        }
+
+        // Over the edge. In 3D, with the cube going from -n to n so that the
+        // middle of each tile is a whole number, the tile on the face ahead
+        // is half a tile further on and half a tile in from the old face.
+        let heading = face.heading(dir);
+        let here = face.normal * n + face.east * (2 * i + 1 - n) + face.south * (2 * j + 1 - n);
+        let there = here + heading - face.normal;
+        let next = self.faces.iter().find(|f| f.normal == heading).unwrap();
+        let dir = *Direction::ALL
+            .iter()
+            .find(|&&d| next.heading(d) == -face.normal)
+            .unwrap();
+        let (i, j) = (
+            (dot(there, next.east) + n - 1) / 2,
+            (dot(there, next.south) + n - 1) / 2,
+        );
+        return (
+            Point::new(next.corner.x + i as usize, next.corner.y + j as usize),
+            dir,
+        );
     }
 }
 
+advent_of_code::example_tests!(Day, "2022/day22");
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example_works() {
-        let mut input = Vec::from([
-            "2,2,2", "1,2,2", "3,2,2", "2,1,2", "2,3,2", "2,2,1", "2,2,3", "2,2,4", "2,2,6",
-            "1,2,5", "3,2,5", "2,1,5", "2,3,5",
+        let input = Vec::from([
+            "        ...#",
+            "        .#..",
+            "        #...",
+            "        ....",
+            "...#.......#",
+            "........#...",
+            "..#....#....",
+            "..........#.",
+            "        ...#....",
+            "        .....#..",
+            "        .#......",
+            "        ......#.",
+            "",
+            "10R5L5R10L4R5L5",
         ])
         .iter()
-        .map(|&x| Cube::parse(x))
-        .collect::<Vec<Cube>>();
+        .map(|&x| String::from(x))
+        .collect::<Vec<String>>();
+
+        assert_eq!(6032, part_1(&input), "Part 1");
+        assert_eq!(5031, part_2(&input), "Part 2");
+    }
+
+    /// A net with no walls, with a `size` square face wherever `layout` has a
+    /// `#`.
+    fn blank_net(layout: &[&str], size: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for row in layout {
+            let line = row
+                .chars()
+                .map(|c| match c {
+                    '#' => OK.to_string().repeat(size),
+                    _ => WRAP.to_string().repeat(size),
+                })
+                .collect::<String>();
+            for _ in 0..size {
+                lines.push(line.clone());
+            }
+        }
+        return lines;
+    }
 
-        {
-            let exposed = count_exposed(&mut input);
-            println!("{:?}", input);
-            assert_eq!(64, exposed, "Count exposed");
+    #[test]
+    fn walks_round_any_net() {
+        let layouts: [&[&str]; 3] = [
+            // The example's.
+            &["  #", "###", "  ##"],
+            // The usual real input's.
+            &[" ##", " #", "##", "#"],
+            &[" #", "###", " #", " #"],
+        ];
+        let size = 4;
+        for layout in layouts {
+            let lines = blank_net(layout, size);
+            let map = lines.iter().map(|l| l.as_str()).collect::<Vec<&str>>();
+            let cube = Cube::fold(&map);
+            for face in cube.faces.iter() {
+                for y in face.corner.y..face.corner.y + size {
+                    for x in face.corner.x..face.corner.x + size {
+                        for dir in Direction::ALL {
+                            let start = (Point::new(x, y), dir);
+
+                            // Going all the way round the cube comes back
+                            // to the start.
+                            let mut at = start;
+                            for _ in 0..4 * size {
+                                at = cube.step(at.0, at.1);
+                            }
+                            assert_eq!(at, start, "going round {:?}", layout);
+
+                            // And so does turning back after one step.
+                            let (p, d) = cube.step(start.0, dir);
+                            let (p, d) = cube.step(p, d.opposite());
+                            assert_eq!((p, d.opposite()), start, "stepping back {:?}", layout);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn folds_real_input_net() {
+        //  AB
+        //  C
+        // DE
+        // F
+        let lines = blank_net(&[" ##", " #", "##", "#"], 4);
+        let map = lines.iter().map(|l| l.as_str()).collect::<Vec<&str>>();
+        let cube = Cube::fold(&map);
+        let tests = [
+            // Off the top of A onto the left of F.
+            ((5, 0), Direction::North, (0, 13), Direction::East),
+            // Off the right of B onto the right of E, upside down.
+            ((11, 1), Direction::East, (7, 10), Direction::West),
+            // Off the bottom of B onto the right of C.
+            ((9, 3), Direction::South, (7, 5), Direction::West),
+            // Off the left of C onto the top of D.
+            ((4, 6), Direction::West, (2, 8), Direction::South),
+            // Across the net's own edge from A down to C.
+            ((5, 3), Direction::South, (5, 4), Direction::South),
+        ];
+        for ((x, y), dir, (want_x, want_y), want_dir) in tests {
+            assert_eq!(
+                cube.step(Point::new(x, y), dir),
+                (Point::new(want_x, want_y), want_dir),
+                "({},{}) going {}",
+                x,
+                y,
+                dir
+            );
         }
     }
 }
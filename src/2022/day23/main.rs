@@ -1,27 +1,46 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::point::{Direction, Point};
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fmt;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 23;
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> i64 {
+        part_1(lines)
+    }
 
-    {
-        println!("Part 1: {}", part_1(&lines));
+    fn part2(lines: &Self::Input) -> usize {
+        part_2(lines)
     }
-    {
-        // 1020 is too low
-        println!("Part 2: {}", part_2(&lines));
+
+    fn visualize(lines: &Self::Input, viz: &mut Visualizer) {
+        let mut grid = parse_grid(lines);
+        viz.frame(&draw_grid(&grid));
+        let mut round = 0;
+        while perform_round(&mut grid, round) {
+            viz.frame(&draw_grid(&grid));
+            round += 1;
+        }
     }
 }
 
@@ -42,34 +61,23 @@ fn part_2(lines: &Vec<String>) -> usize {
     return round + 1;
 }
 
-fn locations_to_check(p: Point, d: usize) -> [Point; 3] {
-    match d {
-        N => [p.to(NW), p.to(N), p.to(NE)],
-        W => [p.to(SW), p.to(W), p.to(NW)],
-        S => [p.to(SW), p.to(S), p.to(SE)],
-        E => [p.to(SE), p.to(E), p.to(NE)],
-        _ => {
-            assert!(false, "invalid direction: {}", d);
-            [p, p, p]
-        }
-    }
+fn locations_to_check(p: Point<i64>, d: Direction) -> [Point<i64>; 3] {
+    let ahead = p.step(d);
+    return [ahead.step(d.turn_left()), ahead, ahead.step(d.turn_right())];
 }
 
 fn perform_round(grid: &mut Grid, round: usize) -> bool {
-    let mut dst2src: HashMap<Point, Point> = HashMap::new();
-    let mut conflicts: HashSet<Point> = HashSet::new();
+    let mut dst2src: HashMap<Point<i64>, Point<i64>> = HashMap::new();
+    let mut conflicts: HashSet<Point<i64>> = HashSet::new();
 
     for &p in grid.iter() {
-        if [N, S, W, E, NW, SW, NE, SE]
-            .iter()
-            .all(|&l| !grid.contains(&p.to(l)))
-        {
+        if p.neighbors8().iter().all(|l| !grid.contains(l)) {
             continue;
         }
         for d in 0..DIRECTIONS.len() {
             let dir = DIRECTIONS[(round + d) % DIRECTIONS.len()];
             if locations_to_check(p, dir).iter().all(|l| !grid.contains(l)) {
-                let dst = p.to(dir);
+                let dst = p.step(dir);
                 if dst2src.contains_key(&dst) {
                     conflicts.insert(dst);
                 } else {
@@ -93,12 +101,12 @@ fn perform_round(grid: &mut Grid, round: usize) -> bool {
 }
 
 fn count_empty(grid: &Grid) -> i64 {
-    let south = get_most(&grid, S);
-    let north = get_most(&grid, N);
-    let west = get_most(&grid, W);
-    let east = get_most(&grid, E);
+    let south = get_most(grid, Direction::South);
+    let north = get_most(grid, Direction::North);
+    let west = get_most(grid, Direction::West);
+    let east = get_most(grid, Direction::East);
     let mut empty = 0;
-    for y in (south..=north).rev() {
+    for y in north..=south {
         for x in west..=east {
             if !grid.contains(&Point::new(x, y)) {
                 empty += 1;
@@ -108,118 +116,71 @@ fn count_empty(grid: &Grid) -> i64 {
     return empty;
 }
 
-const W: usize = 0;
-const E: usize = 1;
-const N: usize = 2;
-const S: usize = 3;
-const NW: usize = 4;
-const NE: usize = 5;
-const SW: usize = 6;
-const SE: usize = 7;
-
-const DIRECTIONS: [usize; 4] = [N, S, W, E];
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
 
 const ELF: char = '#';
-const _EMPTY: char = '.';
-
-type Grid = HashSet<Point>;
-
-// To make it easier not to mess up x and y.
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
-pub struct Point {
-    x: i64, // column
-    y: i64, // row
-}
-
-impl Point {
-    pub fn new(x: i64, y: i64) -> Self {
-        Self { x: x, y: y }
-    }
-
-    fn to(&self, d: usize) -> Point {
-        match d {
-            N => Point::new(self.x, self.y + 1),
-            W => Point::new(self.x - 1, self.y),
-            E => Point::new(self.x + 1, self.y),
-            S => Point::new(self.x, self.y - 1),
-            NW => Point::new(self.x - 1, self.y + 1),
-            NE => Point::new(self.x + 1, self.y + 1),
-            SW => Point::new(self.x - 1, self.y - 1),
-            SE => Point::new(self.x + 1, self.y - 1),
-            _ => {
-                assert!(false, "invalid direction: {}", d);
-                Point::new(0, 0)
-            }
-        }
-    }
-}
-
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
-}
-
-fn _print_grid(grid: &Grid) {
-    let south = get_most(grid, S);
-    let north = get_most(grid, N);
-    let west = get_most(grid, W);
-    let east = get_most(grid, E);
-    println!("Grid: x[{},{}] y[{},{}]", west, east, south, north);
-    for y in (south..=north).rev() {
-        for x in west..=east {
-            if grid.contains(&Point::new(x, y)) {
-                print!("{}", ELF);
-            } else {
-                print!("{}", _EMPTY);
-            }
+const EMPTY: char = '.';
+
+type Grid = HashSet<Point<i64>>;
+
+fn draw_grid(grid: &Grid) -> Frame {
+    let north_west = Point::new(
+        get_most(grid, Direction::West),
+        get_most(grid, Direction::North),
+    );
+    let south_east = Point::new(
+        get_most(grid, Direction::East),
+        get_most(grid, Direction::South),
+    );
+    return viz::draw(north_west, south_east, |p| {
+        if grid.contains(&p) {
+            return Cell::colored(ELF, Rgb(90, 200, 90));
         }
-        println!("");
-    }
+        return Cell::from(EMPTY);
+    });
 }
 
-fn get_most(grid: &Grid, dir: usize) -> i64 {
+fn get_most(grid: &Grid, dir: Direction) -> i64 {
     match dir {
-        N => return grid.iter().map(|&p| p.y).max().unwrap(),
-        S => return grid.iter().map(|&p| p.y).min().unwrap(),
-        W => return grid.iter().map(|&p| p.x).min().unwrap(),
-        E => return grid.iter().map(|&p| p.x).max().unwrap(),
-        _ => assert!(false, "invalid direction: {}", dir),
+        Direction::North => return grid.iter().map(|&p| p.y).min().unwrap(),
+        Direction::South => return grid.iter().map(|&p| p.y).max().unwrap(),
+        Direction::West => return grid.iter().map(|&p| p.x).min().unwrap(),
+        Direction::East => return grid.iter().map(|&p| p.x).max().unwrap(),
     }
-    return 0;
 }
 
 fn parse_grid(lines: &Vec<String>) -> Grid {
     let mut grid: Grid = Grid::new();
-    let mut y: i64 = 0;
-    for line in lines {
+    for (y, line) in lines.iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
-            match c {
-                ELF => _ = grid.insert(Point::new(x as i64, y)),
-                _ => (),
+            if c == ELF {
+                grid.insert(Point::new(x as i64, y as i64));
             }
         }
-
-        y -= 1;
     }
     return grid;
 }
 
 fn _eq_grid(a: &Grid, b: &Grid) {
-    let a_south = get_most(a, S);
-    let a_north = get_most(a, N);
-    let a_west = get_most(a, W);
-    let a_east = get_most(a, E);
-    let b_south = get_most(b, S);
-    let b_north = get_most(b, N);
-    let b_west = get_most(b, W);
-    let b_east = get_most(b, E);
-    assert_eq!(a_north - a_south, b_north - b_south);
+    let a_south = get_most(a, Direction::South);
+    let a_north = get_most(a, Direction::North);
+    let a_west = get_most(a, Direction::West);
+    let a_east = get_most(a, Direction::East);
+    let b_south = get_most(b, Direction::South);
+    let b_north = get_most(b, Direction::North);
+    let b_west = get_most(b, Direction::West);
+    let b_east = get_most(b, Direction::East);
+    assert_eq!(a_south - a_north, b_south - b_north);
     assert_eq!(a_east - a_west, b_east - b_west);
-    for y in 0..=(a_north - a_south) {
+    for y in 0..=(a_south - a_north) {
         for x in 0..=(a_east - a_west) {
-            let ac = a.contains(&Point::new(a_west + x, a_south + y));
-            let bc = b.contains(&Point::new(b_west + x, b_south + y));
+            let ac = a.contains(&Point::new(a_west + x, a_north + y));
+            let bc = b.contains(&Point::new(b_west + x, b_north + y));
             assert!(
                 (ac && bc) || (!ac && !bc),
                 "Grid A = {}, Grid B = {}, (x={},y={})",
@@ -232,10 +193,16 @@ fn _eq_grid(a: &Grid, b: &Grid) {
     }
 }
 
+advent_of_code::example_tests!(Day, "2022/day23");
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn str_vec(v: &Vec<&str>) -> Vec<String> {
+        v.iter().map(|x| x.to_string()).collect()
+    }
+
     #[test]
     fn example_works() {
         let input = str_vec(&Vec::from([
@@ -0,0 +1,248 @@
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::grid::Grid;
+use advent_of_code::utils::math;
+use advent_of_code::utils::point::{Direction, Point};
+use advent_of_code::utils::search;
+
+fn main() {
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 24;
+
+    type Input = Valley;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Valley::parse(lines)
+    }
+
+    fn part1(valley: &Self::Input) -> usize {
+        valley.trip(&[valley.end]).expect("no way across")
+    }
+
+    fn part2(valley: &Self::Input) -> usize {
+        // Back for the snacks and across again.
+        let waypoints = [valley.end, valley.start, valley.end];
+        valley.trip(&waypoints).expect("no way across")
+    }
+}
+
+/// The inside of the valley, not counting its walls, so `(0,0)` is the top
+/// left spot a blizzard can be in. The way in is just above the top row and
+/// the way out just below the bottom one.
+#[derive(Debug, Clone)]
+pub struct Valley {
+    start: Point<i64>,
+    end: Point<i64>,
+    /// Where the blizzards are `t` minutes in, for every `t` until they're
+    /// all back where they started.
+    occupied: Vec<Grid<bool>>,
+}
+
+impl Valley {
+    pub fn parse(lines: &[String]) -> Result<Self, AocError> {
+        if lines.len() < 3 {
+            return Err(AocError::parse(lines.len(), "the valley needs walls"));
+        }
+        let gap = |line_no: usize| {
+            lines[line_no - 1]
+                .chars()
+                .position(|c| c == '.')
+                .map(|x| x as i64 - 1)
+                .ok_or_else(|| AocError::parse(line_no, "no gap in the wall"))
+        };
+        let start = Point::new(gap(1)?, -1);
+        let end = Point::new(gap(lines.len())?, lines.len() as i64 - 2);
+
+        // With no room between the walls there'd be nowhere to go, and the
+        // blizzards would repeat every 0 minutes.
+        let inside = &lines[1..lines.len() - 1];
+        let Some(width) = inside[0].chars().count().checked_sub(2).filter(|&w| w > 0) else {
+            return Err(AocError::parse(
+                2,
+                "the valley needs to be at least one tile wide",
+            ));
+        };
+        let height = inside.len();
+        let mut blizzards = Vec::new();
+        for (y, line) in inside.iter().enumerate() {
+            let line_no = y + 2;
+            if line.chars().count() != width + 2 {
+                return Err(AocError::parse(line_no, "rows are different lengths"));
+            }
+            for (x, c) in line.chars().skip(1).take(width).enumerate() {
+                let dir = match c {
+                    '.' => continue,
+                    '^' => Direction::North,
+                    '>' => Direction::East,
+                    'v' => Direction::South,
+                    '<' => Direction::West,
+                    _ => return Err(AocError::parse(line_no, format!("unknown tile {:?}", c))),
+                };
+                blizzards.push((Point::new(x as i64, y as i64), dir));
+            }
+        }
+
+        let period = math::lcm(width as i64, height as i64) as usize;
+        let occupied = (0..period)
+            .map(|t| {
+                let mut grid = Grid::new(width, height, false);
+                for &(p, dir) in &blizzards {
+                    let moved = p + dir.delta::<i64>() * t as i64;
+                    let wrapped = Point::new(
+                        moved.x.rem_euclid(width as i64),
+                        moved.y.rem_euclid(height as i64),
+                    );
+                    grid[wrapped] = true;
+                }
+                grid
+            })
+            .collect();
+
+        return Ok(Self {
+            start,
+            end,
+            occupied,
+        });
+    }
+
+    /// Whether there's somewhere to stand at `p` after `t` minutes.
+    fn is_open(&self, p: Point<i64>, t: usize) -> bool {
+        if p == self.start || p == self.end {
+            return true;
+        }
+        let t = t % self.occupied.len();
+        return self.occupied[t].get(p) == Some(&false);
+    }
+
+    /// The first minute `to` can be reached, setting off from `from` at
+    /// `minute`. Since the blizzards repeat, a spot at one time is the same
+    /// as that spot a whole period later, which keeps the search finite.
+    fn crossing(&self, from: Point<i64>, to: Point<i64>, minute: usize) -> Option<usize> {
+        let period = self.occupied.len();
+        let (goal, search) = search::astar(
+            (from, minute % period),
+            |&(p, t)| {
+                let t = (t + 1) % period;
+                // Waiting where we are is a move too.
+                [p].into_iter()
+                    .chain(p.neighbors4())
+                    .filter(|&next| self.is_open(next, t))
+                    .map(|next| ((next, t), 1))
+                    .collect::<Vec<((Point<i64>, usize), usize)>>()
+            },
+            |&(p, _)| p.manhattan(to) as usize,
+            |&(p, _)| p == to,
+        )?;
+        return Some(minute + search.distance(&goal).unwrap());
+    }
+
+    /// How many minutes it takes to go from the start through each of
+    /// `waypoints` in turn, or `None` if the blizzards never let us through.
+    pub fn trip(&self, waypoints: &[Point<i64>]) -> Option<usize> {
+        let mut at = self.start;
+        let mut minute = 0;
+        for &next in waypoints {
+            minute = self.crossing(at, next, minute)?;
+            at = next;
+        }
+        return Some(minute);
+    }
+}
+
+advent_of_code::example_tests!(Day, "2022/day24");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valley(lines: &[&str]) -> Valley {
+        let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        return Valley::parse(&lines).unwrap();
+    }
+
+    #[test]
+    fn test_blizzards() {
+        let v = valley(&[
+            "#.#####", //
+            "#.....#", //
+            "#>....#", //
+            "#.....#", //
+            "#...v.#", //
+            "#.....#", //
+            "#####.#", //
+        ]);
+        let (width, height) = (v.occupied[0].width(), v.occupied[0].height());
+        assert_eq!((width, height), (5, 5));
+        assert_eq!((v.start, v.end), (Point::new(0, -1), Point::new(4, 5)));
+        assert_eq!(v.occupied.len(), 5);
+
+        let tests = [
+            (0, vec![(0, 1), (3, 3)]),
+            (1, vec![(1, 1), (3, 4)]),
+            // Both blizzards on the same spot.
+            (3, vec![(3, 1)]),
+            // Wrapped round to the other side.
+            (5, vec![(0, 1), (3, 3)]),
+        ];
+        for (t, want) in tests {
+            let mut got = (0..height as i64)
+                .flat_map(|y| (0..width as i64).map(move |x| Point::new(x, y)))
+                .filter(|&p| !v.is_open(p, t))
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<(i64, i64)>>();
+            got.sort_by_key(|&(x, y)| (y, x));
+            assert_eq!(got, want, "after {} minutes", t);
+        }
+        // The walls are never open, and the way in and out always are.
+        assert!(!v.is_open(Point::new(-1, 0), 0));
+        assert!(!v.is_open(Point::new(1, -1), 0));
+        assert!(v.is_open(v.start, 0) && v.is_open(v.end, 0));
+    }
+
+    #[test]
+    fn test_trip() {
+        let v = valley(&[
+            "#.######", //
+            "#>>.<^<#", //
+            "#.<..<<#", //
+            "#>v.><>#", //
+            "#<^v^^>#", //
+            "######.#", //
+        ]);
+        assert_eq!(v.trip(&[v.end]), Some(18));
+        assert_eq!(v.crossing(v.end, v.start, 18), Some(18 + 23));
+        assert_eq!(v.trip(&[v.end, v.start, v.end]), Some(18 + 23 + 13));
+        // Going nowhere takes no time.
+        assert_eq!(v.trip(&[]), Some(0));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec!["#.#", "#.#"], 2),
+            (vec!["###", "#.#", "#.#"], 1),
+            (vec!["#.##", "#.x#", "##.#"], 2),
+            (vec!["#.##", "#..#", "#...#", "##.#"], 3),
+            (vec!["#.#", "##", "#.#"], 2),
+            (vec!["#.", "#", "#."], 2),
+            (vec!["#.#", "#.#", "#", "#.#"], 3),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Valley::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
+        }
+    }
+}
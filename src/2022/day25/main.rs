@@ -1,23 +1,32 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::assertions_on_constants, clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::{Solution, Unsolved};
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    {
-        println!(
-            "Part 1: {}",
-            to_snafu(lines.iter().map(|x| from_snafu(x)).sum::<i64>())
-        );
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 25;
+
+    type Input = Vec<String>;
+    type Part1 = String;
+    type Part2 = Unsolved;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> String {
+        to_snafu(lines.iter().map(|x| from_snafu(x)).sum::<i64>())
+    }
+
+    fn part2(_lines: &Self::Input) -> Unsolved {
+        Unsolved
     }
 }
 
@@ -28,7 +37,7 @@ fn from_snafu(s: &str) -> i64 {
         match c {
             '=' => res += -2 * mult,
             '-' => res += -mult,
-            _ => res += ((c as u8 - '0' as u8) as i64) * mult,
+            _ => res += ((c as u8 - b'0') as i64) * mult,
         }
         mult *= 5;
     }
@@ -40,14 +49,14 @@ fn to_snafu(v: i64) -> String {
     let mut v5 = v;
     while v5 > 0 {
         base5.push((v5 % 5) as u8);
-        v5 = v5 / 5;
+        v5 /= 5;
     }
 
     // Already in snafu format.
     if base5.iter().all(|&x| x <= 2) {
         return base5
             .iter()
-            .map(|x| (x + '0' as u8) as char)
+            .map(|x| (x + b'0') as char)
             .rev()
             .collect::<String>();
     }
@@ -71,7 +80,7 @@ fn to_snafu(v: i64) -> String {
     return conv
         .iter()
         .map(|&x| match x {
-            0 | 1 | 2 => ((x as u8) + ('0' as u8)) as char,
+            0..=2 => ((x as u8) + b'0') as char,
             -1 => '-',
             -2 => '=',
             _ => {
@@ -1,22 +1,33 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let pt1: i64 = lines.iter().map(|line| parse_line_pt1(line)).sum();
-    let pt2: i64 = lines.iter().map(|line| parse_line_pt2(line)).sum();
-    println!("Part 1: {}", pt1);
-    println!("Part 1: {}", pt2);
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> i64 {
+        lines.iter().map(|line| parse_line_pt1(line)).sum()
+    }
+
+    fn part2(lines: &Self::Input) -> i64 {
+        lines.iter().map(|line| parse_line_pt2(line)).sum()
+    }
 }
 
 fn parse_line_pt1(s: &str) -> i64 {
@@ -32,7 +43,7 @@ fn found_to_digit(s: &str, options: &[(Option<usize>, i32)], find_min: bool) ->
     let mut mval: i64 = -1;
     for (index, value) in options {
         if let Some(i) = index {
-            if mindex == None
+            if mindex.is_none()
                 || (find_min && *i < mindex.unwrap())
                 || (!find_min && *i > mindex.unwrap())
             {
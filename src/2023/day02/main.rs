@@ -1,102 +1,91 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return)]
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
 
-    let pt1: i64 = lines.iter().map(|line| parse_line_pt1(line)).sum();
-    println!("Part 1: {}", pt1);
-    let pt2: i64 = lines.iter().map(|line| parse_line_pt2(line)).sum();
-    println!("Part 2: {}", pt2);
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn parse_line_pt1(s: &str) -> i64 {
-    let max_red = 12;
-    let max_green = 13;
-    let max_blue = 14;
+pub struct Day;
 
-    let game_id_re = Regex::new(r"Game (\d+)").unwrap();
-    assert!(game_id_re.is_match(s));
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
 
-    let mut game_id = 0;
-    for m in game_id_re.captures_iter(s) {
-        for (i, capt) in m.iter().enumerate() {
-            if i == 0 {
-                continue;
-            }
+    type Input = Vec<Game>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-            if let Some(sub) = capt {
-                game_id = str2i64(sub.as_str());
-            }
-        }
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Game::parse(i + 1, line))
+            .collect()
     }
 
-    let possible = color_is_possible(s, "green", max_green)
-        && color_is_possible(s, "blue", max_blue)
-        && color_is_possible(s, "red", max_red);
-
-    if possible {
-        return game_id;
+    fn part1(games: &Self::Input) -> i64 {
+        games.iter().map(parse_line_pt1).sum()
     }
 
-    return 0;
+    fn part2(games: &Self::Input) -> i64 {
+        games.iter().map(parse_line_pt2).sum()
+    }
 }
 
-fn parse_line_pt2(s: &str) -> i64 {
-    return color_minimum(s, "green") * color_minimum(s, "blue") * color_minimum(s, "red");
+#[derive(Debug, Clone)]
+pub struct Game {
+    id: i64,
+    /// Every count of cubes shown, with its color, from all the handfuls.
+    cubes: Vec<(i64, String)>,
 }
 
-fn color_is_possible(s: &str, color: &str, max_count: i64) -> bool {
-    let color_re = Regex::new(&format!(r"(\d+) {}", color)).unwrap();
-    for m in color_re.captures_iter(s) {
-        for (i, capt) in m.iter().enumerate() {
-            if i == 0 {
-                continue;
-            }
-
-            if let Some(sub) = capt {
-                let count = str2i64(sub.as_str());
-                if count > max_count {
-                    return false;
-                }
-            }
-        }
+impl Game {
+    pub fn parse(line_no: usize, s: &str) -> Result<Self, AocError> {
+        let (id, handfuls) = scan!(line_no, s, "Game {}: {}" => i64, String)?;
+        let cubes = handfuls
+            .split([';', ','])
+            .map(|cube| scan!(line_no, cube.trim(), "{} {}" => i64, String))
+            .collect::<Result<Vec<(i64, String)>, AocError>>()?;
+        return Ok(Self { id, cubes });
+    }
+
+    fn counts<'a>(&'a self, color: &'a str) -> impl Iterator<Item = i64> + 'a {
+        self.cubes
+            .iter()
+            .filter(move |(_, c)| c == color)
+            .map(|(count, _)| *count)
     }
-    return true;
 }
 
-fn color_minimum(s: &str, color: &str) -> i64 {
-    let mut smallest = 0;
-    let color_re = Regex::new(&format!(r"(\d+) {}", color)).unwrap();
-    for m in color_re.captures_iter(s) {
-        for (i, capt) in m.iter().enumerate() {
-            if i == 0 {
-                continue;
-            }
-
-            if let Some(sub) = capt {
-                let count = str2i64(sub.as_str());
-                if count > smallest {
-                    smallest = count;
-                }
-            }
-        }
+fn parse_line_pt1(game: &Game) -> i64 {
+    let max_red = 12;
+    let max_green = 13;
+    let max_blue = 14;
+
+    let possible = color_is_possible(game, "green", max_green)
+        && color_is_possible(game, "blue", max_blue)
+        && color_is_possible(game, "red", max_red);
+
+    if possible {
+        return game.id;
     }
-    return smallest;
+
+    return 0;
 }
 
-fn str2i64(s: &str) -> i64 {
-    return s.parse::<i64>().unwrap();
+fn parse_line_pt2(game: &Game) -> i64 {
+    return color_minimum(game, "green") * color_minimum(game, "blue") * color_minimum(game, "red");
+}
+
+fn color_is_possible(game: &Game, color: &str, max_count: i64) -> bool {
+    game.counts(color).all(|count| count <= max_count)
+}
+
+fn color_minimum(game: &Game, color: &str) -> i64 {
+    game.counts(color).max().unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -123,7 +112,8 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            assert_eq!(parse_line_pt1(input), want, "for input {}", input);
+            let game = Game::parse(1, input).unwrap();
+            assert_eq!(parse_line_pt1(&game), want, "for input {}", input);
         }
     }
 
@@ -147,7 +137,15 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            assert_eq!(parse_line_pt2(input), want, "for input {}", input);
+            let game = Game::parse(1, input).unwrap();
+            assert_eq!(parse_line_pt2(&game), want, "for input {}", input);
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Game::parse(1, "Game x: 3 blue").is_err());
+        assert!(Game::parse(1, "Game 1: 3 blue; four red").is_err());
+        assert!(Game::parse(1, "Game 1 3 blue").is_err());
+    }
 }
@@ -1,26 +1,37 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::len_zero, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
 
-    let pt1: i32 = handle_pt1(&lines);
-    println!("Part 1: {}", pt1);
-    let pt2: i32 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+    fn part1(lines: &Self::Input) -> i32 {
+        handle_pt1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> i32 {
+        handle_pt2(lines)
+    }
 }
 
 fn handle_pt1(lines: &Vec<String>) -> i32 {
@@ -164,6 +175,12 @@ pub struct MaybePart {
     pub val: i32,
 }
 
+impl Default for MaybePart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaybePart {
     pub fn new() -> Self {
         MaybePart {
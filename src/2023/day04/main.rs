@@ -1,129 +1,93 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::parse::ints;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Card::parse(i + 1, line))
+            .collect()
+    }
+
+    fn part1(cards: &Self::Input) -> i32 {
+        handle_pt1(cards)
+    }
 
-    let pt1: i32 = handle_pt1(&lines);
-    println!("Part 1: {}", pt1);
-    let pt2: i32 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+    fn part2(cards: &Self::Input) -> i32 {
+        handle_pt2(cards)
+    }
 }
 
-fn parse_line_pt1(line: &str) -> i32 {
-    let line_re = Regex::new(r"Card\s+(\d+):\s+(.*)\s+\|\s+(.*)").unwrap();
-    let mut winning_set: HashSet<i32> = HashSet::new();
-
-    let mut match_count = 0;
-    for m in line_re.captures_iter(line) {
-        for (i, capt) in m.iter().enumerate() {
-            if let Some(sub) = capt {
-                match i {
-                    2 => {
-                        for n in sub
-                            .as_str()
-                            .split(" ")
-                            .filter(|x| x.len() > 0)
-                            .map(|x| x.parse::<i32>().unwrap())
-                        {
-                            winning_set.insert(n);
-                        }
-                    }
-                    3 => {
-                        for n in sub
-                            .as_str()
-                            .split(" ")
-                            .filter(|x| x.len() > 0)
-                            .map(|x| x.parse::<i32>().unwrap())
-                        {
-                            if winning_set.contains(&n) {
-                                match_count += 1;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+#[derive(Debug, Clone)]
+pub struct Card {
+    id: i32,
+    /// How many of the numbers we have are winning ones.
+    matches: u32,
+}
+
+impl Card {
+    pub fn parse(line_no: usize, s: &str) -> Result<Self, AocError> {
+        let (id, winning, have) = scan!(line_no, s, "Card {}: {} | {}" => String, String, String)?;
+        let winning = ints::<i32>(line_no, &winning)?.into_iter().collect::<HashSet<i32>>();
+        let matches = ints::<i32>(line_no, &have)?
+            .iter()
+            .filter(|n| winning.contains(n))
+            .count();
+        return Ok(Self {
+            id: parse_at(line_no, id.trim())?,
+            matches: matches as u32,
+        });
     }
+}
 
-    if match_count == 0 {
+fn parse_line_pt1(card: &Card) -> i32 {
+    if card.matches == 0 {
         return 0;
     }
 
     let base: i32 = 2;
-    base.pow(match_count - 1)
+    base.pow(card.matches - 1)
 }
 
-fn handle_pt1(lines: &Vec<String>) -> i32 {
-    lines.iter().map(|l| parse_line_pt1(l)).sum()
+fn handle_pt1(cards: &Vec<Card>) -> i32 {
+    cards.iter().map(parse_line_pt1).sum()
 }
 
-fn parse_line_pt2(line: &str) -> (i32, Vec<i32>) {
-    let line_re = Regex::new(r"Card\s+(\d+):\s+(.*)\s+\|\s+(.*)").unwrap();
-    let mut winning_set: HashSet<i32> = HashSet::new();
-
-    let mut card_id = 0;
-    let mut match_count = 0;
-    for m in line_re.captures_iter(line) {
-        for (i, capt) in m.iter().enumerate() {
-            if let Some(sub) = capt {
-                match i {
-                    1 => card_id = sub.as_str().parse::<i32>().unwrap(),
-                    2 => {
-                        for n in sub
-                            .as_str()
-                            .split(" ")
-                            .filter(|x| x.len() > 0)
-                            .map(|x| x.parse::<i32>().unwrap())
-                        {
-                            winning_set.insert(n);
-                        }
-                    }
-                    3 => {
-                        for n in sub
-                            .as_str()
-                            .split(" ")
-                            .filter(|x| x.len() > 0)
-                            .map(|x| x.parse::<i32>().unwrap())
-                        {
-                            if winning_set.contains(&n) {
-                                match_count += 1;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-
+fn parse_line_pt2(card: &Card) -> (i32, Vec<i32>) {
     (
-        card_id,
-        (1..=match_count)
-            .into_iter()
-            .map(|x| card_id + x)
+        card.id,
+        (1..=card.matches as i32)
+            .map(|x| card.id + x)
             .collect::<Vec<i32>>(),
     )
 }
 
-fn handle_pt2(lines: &Vec<String>) -> i32 {
+fn handle_pt2(cards: &Vec<Card>) -> i32 {
     let mut frequencies: HashMap<i32, i32> = HashMap::new();
     let mut max_id = 0;
-    for line in lines {
-        let (id, copies) = parse_line_pt2(line);
+    for card in cards {
+        let (id, copies) = parse_line_pt2(card);
         *frequencies.entry(id).or_insert(0) += 1;
         if id > max_id {
             max_id = id;
@@ -135,10 +99,7 @@ fn handle_pt2(lines: &Vec<String>) -> i32 {
         }
     }
 
-    (1..=max_id)
-        .into_iter()
-        .map(|id| frequencies.get(&id).unwrap())
-        .sum()
+    (1..=max_id).map(|id| frequencies.get(&id).unwrap()).sum()
 }
 
 #[cfg(test)]
@@ -160,7 +121,8 @@ mod tests {
         )];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt1(&input), want, "for input\n{}", input.join("\n"));
+            let cards = Day::parse(&input).unwrap();
+            assert_eq!(handle_pt1(&cards), want, "for input\n{}", input.join("\n"));
         }
     }
 
@@ -179,7 +141,8 @@ mod tests {
         )];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt2(&input), want, "for input\n{}", input.join("\n"));
+            let cards = Day::parse(&input).unwrap();
+            assert_eq!(handle_pt2(&cards), want, "for input\n{}", input.join("\n"));
         }
     }
 }
@@ -1,31 +1,69 @@
-use clap::Parser;
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::collections::HashSet;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
 
 fn main() {
-    /*
-    Time:        41     96     88     94
-    Distance:   214   1789   1127   1055
-    */
-    let pt1: usize = handle_pt1(vec![(41, 214), (96, 1789), (88, 1127), (94, 1055)]);
-    println!("Part 1: {}", pt1);
-    let pt2: usize = count_hold_start(41968894, 214178911271055);
-    //  let pt2: i32 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+
+    // Time:        41     96     88     94
+    // Distance:   214   1789   1127   1055
+    type Input = (Vec<i64>, Vec<i64>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        let (Some(times), Some(distances)) = (lines.first(), lines.get(1)) else {
+            return Err(AocError::parse(
+                lines.len() + 1,
+                "expected a Time line and a Distance line",
+            ));
+        };
+        Ok((parse_numbers(1, times)?, parse_numbers(2, distances)?))
+    }
+
+    fn part1((times, distances): &Self::Input) -> usize {
+        handle_pt1(
+            times
+                .iter()
+                .copied()
+                .zip(distances.iter().copied())
+                .collect(),
+        )
+    }
+
+    fn part2((times, distances): &Self::Input) -> usize {
+        count_hold_start(concat_digits(times), concat_digits(distances))
+    }
+}
+
+fn parse_numbers(line_no: usize, line: &str) -> Result<Vec<i64>, AocError> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|x| parse_at::<i64>(line_no, x))
+        .collect()
+}
+
+/// The kerning was wrong: the numbers on each line are really one number.
+fn concat_digits(values: &Vec<i64>) -> i64 {
+    values
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<String>()
+        .parse::<i64>()
+        .unwrap()
 }
 
 fn count_hold_start(time: i64, distance: i64) -> usize {
     (0..=time)
-        .into_iter()
         .filter(|start_speed| start_speed * (time - start_speed) > distance)
         .count()
 }
@@ -40,3 +78,5 @@ fn handle_pt1(times: Vec<(i64, i64)>) -> usize {
     }
     res
 }
+
+advent_of_code::example_tests!(Day, "2023/day06");
@@ -1,26 +1,40 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(
+    clippy::len_zero,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::ptr_arg,
+    clippy::redundant_field_names
+)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use std::cmp::Ordering;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    let pt1: i32 = handle_pt1(&lines);
-    // 247766195 too low
-    // 247775171 too low
-    // 247415731 too low
-    println!("Part 1: {}", pt1);
-    let pt2: i32 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+
+    type Input = Vec<String>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> i32 {
+        handle_pt1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> i32 {
+        handle_pt2(lines)
+    }
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -56,7 +70,7 @@ fn sorted_frequency(hand: &Vec<char>) -> Vec<(char, i32)> {
 
     let mut res: Vec<(char, i32)> = Vec::new();
     let mut running_count: i32 = 1;
-    let mut current_card = *sorted_hand.iter().nth(0).unwrap();
+    let mut current_card = *sorted_hand.first().unwrap();
     for i in 1..sorted_hand.len() {
         let c = sorted_hand[i];
         if c == current_card {
@@ -69,7 +83,7 @@ fn sorted_frequency(hand: &Vec<char>) -> Vec<(char, i32)> {
     }
     res.push((current_card, running_count));
 
-    res.sort_by(|(_, count1), (_, count2)| count1.cmp(count2));
+    res.sort_by_key(|(_, count1)| *count1);
     res.reverse();
 
     res
@@ -107,7 +121,7 @@ fn sorted_frequency_2(hand: &Vec<char>) -> Vec<(char, i32)> {
         res.push((current_c, running_count));
     }
 
-    res.sort_by(|(_, count1), (_, count2)| count1.cmp(count2));
+    res.sort_by_key(|(_, count1)| *count1);
     res.reverse();
 
     if res.len() > 0 {
@@ -124,14 +138,11 @@ fn determine_type(hand: &Vec<char>) -> i32 {
     let frequencies = sorted_frequency(hand);
     match frequencies.len() {
         1 => return FIVES,
-        2 => match frequencies.iter().nth(0).unwrap() {
+        2 => match frequencies.first().unwrap() {
             (_, 4) => return FOURS,
             _ => return FULL_HOUSE,
         },
-        3 => match (
-            frequencies.iter().nth(0).unwrap(),
-            frequencies.iter().nth(1).unwrap(),
-        ) {
+        3 => match (frequencies.first().unwrap(), frequencies.get(1).unwrap()) {
             ((_, 2), (_, 2)) => return TWO_PAIRS,
             _ => return THREES,
         },
@@ -145,19 +156,14 @@ fn determine_type_2(hand: &Vec<char>) -> i32 {
     let frequencies = sorted_frequency_2(hand);
     match frequencies.len() {
         1 => return FIVES,
-        2 => match frequencies.iter().nth(0).unwrap() {
+        2 => match frequencies.first().unwrap() {
             (_, 4) => return FOURS,
             _ => return FULL_HOUSE,
         },
-        3 => {
-            match (
-                frequencies.iter().nth(0).unwrap(),
-                frequencies.iter().nth(1).unwrap(),
-            ) {
-                ((_, 2), (_, 2)) => return TWO_PAIRS,
-                _ => return THREES,
-            }
-        }
+        3 => match (frequencies.first().unwrap(), frequencies.get(1).unwrap()) {
+            ((_, 2), (_, 2)) => return TWO_PAIRS,
+            _ => return THREES,
+        },
         4 => return PAIR,
         5 => return HIGH,
         _ => todo!(),
@@ -241,8 +247,8 @@ fn handle_pt1(lines: &Vec<String>) -> i32 {
         .enumerate()
         .map(|(i, hand)| {
             let rank = tot as i32 - i as i32;
-            let value = rank * hand.bid;
-            value
+
+            rank * hand.bid
         })
         .sum()
 }
@@ -285,8 +291,8 @@ fn handle_pt2(lines: &Vec<String>) -> i32 {
         .enumerate()
         .map(|(i, hand)| {
             let rank = tot as i32 - i as i32;
-            let value = rank * hand.bid;
-            value
+
+            rank * hand.bid
         })
         .sum()
 }
@@ -1,134 +1,153 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
-use std::collections::HashMap;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return)]
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::math;
+use std::collections::{HashMap, HashSet};
 
-    let pt1: i32 = handle_pt1(&lines);
-    println!("Part 1: {}", pt1);
-    let pt2: i64 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn parse_guide(line: &String) -> (String, (String, String)) {
-    let line_re = Regex::new(r"(\w+)\s+=\s+\((\w+),\s+(\w+)\)").unwrap();
-
-    let mut source = String::from("");
-    let mut left = String::from("");
-    let mut right = String::from("");
-
-    for m in line_re.captures_iter(line) {
-        for (i, capt) in m.iter().enumerate() {
-            if let Some(sub) = capt {
-                match i {
-                    1 => source = sub.as_str().to_string(),
-                    2 => left = sub.as_str().to_string(),
-                    3 => right = sub.as_str().to_string(),
-                    _ => {}
-                }
-            }
-        }
-    }
+pub struct Day;
 
-    (source, (left, right))
-}
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
 
-fn handle_pt1(lines: &Vec<String>) -> i32 {
-    let directions = lines[0].chars().collect::<Vec<char>>();
+    type Input = Network;
+    type Part1 = i32;
+    type Part2 = i64;
 
-    let mut map: HashMap<String, (String, String)> = HashMap::new();
-    let mut current: String = String::from("AAA");
-    for i in 2..lines.len() {
-        let (source, (left, right)) = parse_guide(&lines[i]);
-        map.insert(source.clone(), (left.clone(), right.clone()));
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Network::parse(lines)
     }
 
-    let mut steps = 0;
-    loop {
-        for dir in &directions {
-            let (left, right) = map.get(&current).unwrap();
-            current = match dir {
-                'L' => left,
-                'R' => right,
-                _ => todo!(),
-            }
-            .clone();
+    fn part1(network: &Self::Input) -> i32 {
+        handle_pt1(network).expect("AAA never gets to ZZZ")
+    }
 
-            steps += 1;
-            if current == "ZZZ" {
-                return steps;
-            }
-        }
+    fn part2(network: &Self::Input) -> i64 {
+        handle_pt2(network).expect("the ghosts never line up")
     }
 }
 
-fn lcm(a: i64, b: i64) -> i64 {
-    (a * b) / gcd(a, b)
+#[derive(Debug, Clone)]
+pub struct Network {
+    /// Each an `L` or an `R`.
+    directions: Vec<char>,
+    /// Where going left and right from each node leads.
+    nodes: HashMap<String, (String, String)>,
 }
 
-fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let tmp = a;
-        a = b;
-        b = tmp % b;
+impl Network {
+    pub fn parse(lines: &[String]) -> Result<Self, AocError> {
+        let Some(first) = lines.first() else {
+            return Err(AocError::parse(1, "no directions"));
+        };
+        let directions = first.chars().collect::<Vec<char>>();
+        if let Some(c) = directions.iter().find(|c| !matches!(c, 'L' | 'R')) {
+            return Err(AocError::parse(1, format!("unknown direction {:?}", c)));
+        }
+
+        let mut nodes = HashMap::new();
+        for (i, line) in lines.iter().enumerate().skip(2) {
+            let (source, left, right) =
+                scan!(i + 1, line, "{} = ({}, {})" => String, String, String)?;
+            nodes.insert(source, (i + 1, left, right));
+        }
+
+        // Every node has to lead somewhere in the network. The first line
+        // that doesn't is the one reported.
+        let missing = nodes
+            .values()
+            .flat_map(|(line_no, left, right)| [(*line_no, left), (*line_no, right)])
+            .filter(|(_, x)| !nodes.contains_key(*x))
+            .min();
+        if let Some((line_no, x)) = missing {
+            return Err(AocError::parse(line_no, format!("no node {:?}", x)));
+        }
+        let nodes = nodes
+            .into_iter()
+            .map(|(source, (_, left, right))| (source, (left, right)))
+            .collect();
+        return Ok(Self { directions, nodes });
     }
-    a
-}
 
-fn handle_pt2(lines: &Vec<String>) -> i64 {
-    let directions = lines[0].chars().collect::<Vec<char>>();
+    /// Where the `step`th move (counting from 0) from `node` leads.
+    fn next(&self, node: &str, step: usize) -> &String {
+        let (left, right) = &self.nodes[node];
+        match self.directions[step % self.directions.len()] {
+            'L' => left,
+            _ => right,
+        }
+    }
+}
 
-    let mut map: HashMap<String, (String, String)> = HashMap::new();
-    let mut current: Vec<String> = Vec::new();
-    for i in 2..lines.len() {
-        let (source, (left, right)) = parse_guide(&lines[i]);
-        map.insert(source.clone(), (left.clone(), right.clone()));
-        if source.ends_with('A') {
-            current.push(source.clone());
+/// How many steps it takes to get from AAA to ZZZ, or `None` if it never
+/// does, which shows as being back at a node at the same point in the
+/// directions.
+fn handle_pt1(network: &Network) -> Option<i32> {
+    let (mut current, _) = network.nodes.get_key_value("AAA")?;
+    let mut seen = HashSet::new();
+    let mut steps = 0;
+    while current != "ZZZ" {
+        if !seen.insert((current, steps % network.directions.len())) {
+            return None;
         }
+        current = network.next(current, steps);
+        steps += 1;
     }
+    return Some(steps as i32);
+}
 
-    let mut first_zs: Vec<i64> = vec![0; current.len()];
-
-    for i in 0..current.len() {
-        let mut c = current[i].clone();
-        let mut steps = 0;
-        loop {
-            let mut break_outer = false;
-            for dir in &directions {
-                let (left, right) = map.get(&c).unwrap();
-                c = match dir {
-                    'L' => left,
-                    'R' => right,
-                    _ => todo!(),
-                }
-                .clone();
-
-                steps += 1;
-                if c.ends_with('Z') {
-                    first_zs[i] = steps;
-                    break_outer = true;
-                    break;
-                }
-            }
-            if break_outer {
-                break;
-            }
+/// When the ghost starting at `start` first gets to a Z, and how long it takes
+/// to get back to one after that. Ghosts are assumed to only ever loop
+/// through one Z. It's `None` if the loop the ghost ends up going round
+/// doesn't have a Z in it.
+fn ghost_loop(network: &Network, start: &String) -> Option<(i64, i64)> {
+    // The step each (node, place in the directions) was first got to on.
+    let mut seen: HashMap<(&String, usize), usize> = HashMap::new();
+    let mut c = start;
+    let mut zs: Vec<usize> = Vec::new();
+    let mut steps = 0;
+    while zs.len() < 2 {
+        let first = *seen
+            .entry((c, steps % network.directions.len()))
+            .or_insert(steps);
+        // Going round again from here only finds the Zs since `first`.
+        if first < steps && zs.last().is_none_or(|&z| z < first) {
+            return None;
+        }
+        c = network.next(c, steps);
+        steps += 1;
+        if c.ends_with('Z') {
+            zs.push(steps);
         }
     }
+    return Some((zs[0] as i64, (zs[1] - zs[0]) as i64));
+}
+
+fn handle_pt2(network: &Network) -> Option<i64> {
+    let current = network
+        .nodes
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .collect::<Vec<&String>>();
+
+    let loops = current
+        .into_iter()
+        .map(|start| ghost_loop(network, start))
+        .collect::<Option<Vec<(i64, i64)>>>()?;
 
-    first_zs.iter().fold(1, |acc: i64, x| lcm(acc, *x))
+    let (step, period) = math::crt(loops.iter().copied())?;
+    // The smallest solution might come before some ghost has got to its loop.
+    let latest = loops.iter().map(|&(first, _)| first).max()?;
+    if step >= latest {
+        return Some(step);
+    }
+    return Some(step + (latest - step + period - 1) / period * period);
 }
 
 #[cfg(test)]
@@ -165,7 +184,13 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt1(&input), want, "for input\n{}", input.join("\n"));
+            let network = Network::parse(&input).unwrap();
+            assert_eq!(
+                handle_pt1(&network),
+                Some(want),
+                "for input\n{}",
+                input.join("\n")
+            );
         }
     }
 
@@ -188,7 +213,52 @@ mod tests {
         )];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt2(&input), want, "for input\n{}", input.join("\n"));
+            let network = Network::parse(&input).unwrap();
+            assert_eq!(
+                handle_pt2(&network),
+                Some(want),
+                "for input\n{}",
+                input.join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn test_never_arrives() {
+        let lines = [
+            "LR",
+            "",
+            "11A = (11B, 11B)",
+            "11B = (11Z, 11A)",
+            "11Z = (11Z, 11Z)",
+            "22A = (22B, 22B)",
+            "22B = (22A, 22A)",
+            "AAA = (BBB, BBB)",
+            "BBB = (ZZZ, AAA)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ];
+        let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let network = Network::parse(&lines).unwrap();
+        // AAA only ever gets to BBB on a right, which leads back, and the
+        // ghost from 22A never leaves 22A and 22B.
+        assert_eq!(handle_pt1(&network), None);
+        assert_eq!(handle_pt2(&network), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec![], 1),
+            (vec!["LRX", "", "AAA = (AAA, AAA)"], 1),
+            (vec!["LR", "", "AAA = (AAA, AAA)", "BBB = BBB, BBB"], 4),
+            (vec!["LR", "", "AAA = (AAA, BBB)", "BBB = (CCC, AAA)"], 4),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Network::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
         }
     }
 }
@@ -1,22 +1,33 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
 
-    let pt1: i64 = handle_pt1(&lines);
-    println!("Part 1: {}", pt1);
-    let pt2: i64 = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+    fn part1(lines: &Self::Input) -> i64 {
+        handle_pt1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> i64 {
+        handle_pt2(lines)
+    }
 }
 
 fn guess_value_end(values: &Vec<i64>) -> i64 {
@@ -36,7 +47,6 @@ fn guess_value_end(values: &Vec<i64>) -> i64 {
 fn parse_line_pt1(line: &String) -> i64 {
     let starting = line
         .split(" ")
-        .into_iter()
         .map(|x| x.parse::<i64>().unwrap())
         .collect::<Vec<i64>>();
 
@@ -44,7 +54,7 @@ fn parse_line_pt1(line: &String) -> i64 {
 }
 
 fn handle_pt1(lines: &Vec<String>) -> i64 {
-    lines.iter().map(|x| parse_line_pt1(x)).sum()
+    lines.iter().map(parse_line_pt1).sum()
 }
 
 fn guess_value_first(values: &Vec<i64>) -> i64 {
@@ -64,7 +74,6 @@ fn guess_value_first(values: &Vec<i64>) -> i64 {
 fn parse_line_pt2(line: &String) -> i64 {
     let starting = line
         .split(" ")
-        .into_iter()
         .map(|x| x.parse::<i64>().unwrap())
         .collect::<Vec<i64>>();
 
@@ -72,42 +81,7 @@ fn parse_line_pt2(line: &String) -> i64 {
 }
 
 fn handle_pt2(lines: &Vec<String>) -> i64 {
-    lines.iter().map(|x| parse_line_pt2(x)).sum()
+    lines.iter().map(parse_line_pt2).sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parsing_pt1() {
-        let tests = [(
-            vec![
-                String::from("0 3 6 9 12 15"),
-                String::from("1 3 6 10 15 21"),
-                String::from("10 13 16 21 30 45"),
-            ],
-            114,
-        )];
-
-        for (input, want) in tests {
-            assert_eq!(handle_pt1(&input), want, "for input\n{}", input.join("\n"));
-        }
-    }
-
-    #[test]
-    fn test_parsing_pt2() {
-        let tests = [(
-            vec![
-                String::from("0 3 6 9 12 15"),
-                String::from("1 3 6 10 15 21"),
-                String::from("10 13 16 21 30 45"),
-            ],
-            2,
-        )];
-
-        for (input, want) in tests {
-            assert_eq!(handle_pt2(&input), want, "for input\n{}", input.join("\n"));
-        }
-    }
-}
+advent_of_code::example_tests!(Day, "2023/day09");
@@ -1,60 +1,94 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::geometry;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
+use advent_of_code::utils::search::bfs;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::fmt;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
 
-    let pt1: i32 = handle_pt1(&lines);
-    println!("Part 1: {}", pt1);
-    let pt2: i32 = handle_pt2(&lines);
-    // 172 too low
-    // 730 too high
-    // 600 too high
-    println!("Part 2: {}", pt2);
-}
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+
+    type Input = Field;
+    type Part1 = i32;
+    type Part2 = i32;
 
-fn dir(p: Point<i32>, x: i32, y: i32) -> Point<i32> {
-    Point {
-        x: p.x + x,
-        y: p.y + y,
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Field::parse(lines)
     }
-}
 
-fn west(p: Point<i32>) -> Point<i32> {
-    dir(p, -1, 0)
-}
-fn east(p: Point<i32>) -> Point<i32> {
-    dir(p, 1, 0)
+    fn part1(field: &Self::Input) -> i32 {
+        handle_pt1(field)
+    }
+
+    fn part2(field: &Self::Input) -> i32 {
+        handle_pt2(field)
+    }
 }
-fn north(p: Point<i32>) -> Point<i32> {
-    dir(p, 0, 1)
+
+pub struct Field {
+    grid: HashMap<Point<i32>, char>,
+    start: Point<i32>,
 }
-fn south(p: Point<i32>) -> Point<i32> {
-    dir(p, 0, -1)
+
+impl Field {
+    pub fn parse(lines: &[String]) -> Result<Self, AocError> {
+        let mut grid: HashMap<Point<i32>, char> = HashMap::new();
+        let mut start: Option<(usize, Point<i32>)> = None;
+        for (row, line) in lines.iter().enumerate() {
+            for (col, shape) in line.chars().enumerate() {
+                let p = Point {
+                    x: col as i32,
+                    y: row as i32,
+                };
+                match shape {
+                    '.' => continue,
+                    'S' if start.is_some() => {
+                        return Err(AocError::parse(row + 1, "more than one start"));
+                    }
+                    'S' => start = Some((row + 1, p)),
+                    '|' | '-' | 'L' | 'J' | '7' | 'F' => {}
+                    _ => {
+                        return Err(AocError::parse(
+                            row + 1,
+                            format!("unknown tile {:?}", shape),
+                        ));
+                    }
+                }
+                grid.insert(p, shape);
+            }
+        }
+
+        let Some((line_no, start)) = start else {
+            return Err(AocError::parse(lines.len(), "no start"));
+        };
+        // Both parts walk out of the start along the loop.
+        if get_neighbors(&grid, start).len() != 2 {
+            return Err(AocError::parse(line_no, "the start should join two pipes"));
+        }
+        return Ok(Self { grid, start });
+    }
 }
 
 fn connections(p: Point<i32>, shape: char) -> Vec<Point<i32>> {
+    let [north, east, south, west] = p.neighbors4();
     match shape {
-        '|' => vec![north(p), south(p)],
-        '-' => vec![west(p), east(p)],
-        'L' => vec![north(p), east(p)],
-        'J' => vec![north(p), west(p)],
-        '7' => vec![south(p), west(p)],
-        'F' => vec![south(p), east(p)],
-        'S' => vec![north(p), south(p), west(p), east(p)],
+        '|' => vec![north, south],
+        '-' => vec![west, east],
+        'L' => vec![north, east],
+        'J' => vec![north, west],
+        '7' => vec![south, west],
+        'F' => vec![south, east],
+        'S' => vec![north, south, west, east],
         _ => vec![],
     }
 }
@@ -63,7 +97,7 @@ fn get_neighbors(grid: &HashMap<Point<i32>, char>, point: Point<i32>) -> Vec<Poi
     connections(point, *grid.get(&point).unwrap())
         .into_iter()
         .filter(|neighbor| {
-            if let Some(neighbor_shape) = grid.get(&neighbor) {
+            if let Some(neighbor_shape) = grid.get(neighbor) {
                 if connections(*neighbor, *neighbor_shape).contains(&point) {
                     return true;
                 }
@@ -73,72 +107,12 @@ fn get_neighbors(grid: &HashMap<Point<i32>, char>, point: Point<i32>) -> Vec<Poi
         .collect::<Vec<Point<i32>>>()
 }
 
-fn handle_pt1(lines: &Vec<String>) -> i32 {
-    let mut grid: HashMap<Point<i32>, char> = HashMap::new();
-    let mut start: Point<i32> = Point { x: 0, y: 0 };
-    for (row, line) in lines.iter().enumerate() {
-        for (col, shape) in line.chars().enumerate() {
-            let p = Point {
-                x: col as i32,
-                y: lines.len() as i32 - row as i32 - 1,
-            };
-            match shape {
-                '.' => continue,
-                'S' => start = p,
-                _ => {}
-            }
-            grid.insert(p, shape);
-        }
-    }
-
-    let mut distances: HashMap<Point<i32>, i32> = HashMap::new();
-    distances.insert(start, 0);
-    let mut queue: Vec<Point<i32>> = vec![start];
-    while !queue.is_empty() {
-        let current = queue.remove(0);
-        let neighbors = get_neighbors(&grid, current);
-        queue.append(
-            &mut neighbors
-                .clone()
-                .into_iter()
-                .filter(|x| !distances.contains_key(x))
-                .collect::<Vec<Point<i32>>>(),
-        );
-
-        if let Some(min_dist) = neighbors
-            .iter()
-            .map(|n| distances.get(n))
-            .filter(|n| n.is_some())
-            .map(|n| n.unwrap())
-            .min()
-        {
-            let new_dist = *min_dist + 1;
-            if let Some(dist) = distances.get(&current) {
-                if *dist < new_dist {
-                    continue;
-                }
-            }
-
-            distances.insert(current, new_dist);
-        }
-    }
+fn handle_pt1(field: &Field) -> i32 {
+    let (grid, start) = (&field.grid, field.start);
 
-    let mut max_distance: i32 = 0;
-    for (row, line) in lines.iter().enumerate() {
-        for (col, _) in line.chars().enumerate() {
-            let p = Point {
-                x: col as i32,
-                y: lines.len() as i32 - row as i32 - 1,
-            };
-            if let Some(dist) = distances.get(&p) {
-                if max_distance < *dist {
-                    max_distance = *dist;
-                }
-            }
-        }
-    }
-
-    max_distance
+    // The farthest point is halfway around the loop.
+    let distances = bfs([start], |&p| get_neighbors(grid, p));
+    *distances.dist.values().max().unwrap() as i32
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
@@ -149,171 +123,35 @@ pub struct TraversablePoint {
 
 impl fmt::Display for TraversablePoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.b.is_some() {
-            return write!(f, "({}, {})", self.a, self.b.unwrap());
+        if let Some(b) = self.b {
+            return write!(f, "({}, {})", self.a, b);
         } else {
             return write!(f, "{}", self.a);
         }
     }
 }
 
-fn in_loop(loop_bounds: &HashSet<Point<i32>>, height: i32, width: i32, point: Point<i32>) -> bool {
-    println!("for point {}", point);
-    let mut p = point;
-    let mut cross = 0;
-    let mut inside = true;
-    while p.x < width {
-        p = east(p);
-        if loop_bounds.contains(&p) {
-            cross += 1;
+fn handle_pt2(field: &Field) -> i32 {
+    let (grid, start) = (&field.grid, field.start);
+
+    // Walk the loop in order, so the tiles are the corners of a polygon.
+    let mut pipes: Vec<Point<i64>> = Vec::new();
+    let mut prev = start;
+    let mut curr = get_neighbors(grid, start)[0];
+    loop {
+        pipes.push(Point::new(curr.x as i64, curr.y as i64));
+        if curr == start {
+            break;
         }
+        let next = get_neighbors(grid, curr)
+            .into_iter()
+            .find(|&n| n != prev)
+            .unwrap();
+        prev = curr;
+        curr = next;
     }
-    println!("  east = {}", cross);
-    inside = inside && cross % 2 == 1;
 
-    p = point;
-    cross = 0;
-    while p.x >= 0 {
-        p = west(p);
-        if loop_bounds.contains(&p) {
-            cross += 1;
-        }
-    }
-    println!("  west = {}", cross);
-    inside = inside && cross % 2 == 1;
-
-    p = point;
-    cross = 0;
-    while p.y >= 0 {
-        p = south(p);
-        if loop_bounds.contains(&p) {
-            cross += 1;
-        }
-    }
-    println!("  south = {}", cross);
-    inside = inside && cross % 2 == 1;
-
-    p = point;
-    cross = 0;
-    while p.y < height {
-        p = north(p);
-        if loop_bounds.contains(&p) {
-            cross += 1;
-        }
-    }
-    println!("  north = {}", cross);
-    inside = inside && cross % 2 == 1;
-
-    inside
-}
-
-fn handle_pt2(lines: &Vec<String>) -> i32 {
-    let height = lines.len() as i32;
-    let width = lines.iter().map(|line| line.len()).max().unwrap() as i32;
-    //  println!("new height is {}, new width is {}", height, width);
-
-    let mut grid: HashMap<Point<i32>, char> = HashMap::new();
-    let mut start: Point<i32> = Point { x: 0, y: 0 };
-    for (row, line) in lines.iter().enumerate() {
-        for (col, shape) in line.chars().enumerate() {
-            let p = Point {
-                x: col as i32,
-                y: lines.len() as i32 - row as i32 - 1,
-            };
-            match shape {
-                'S' => start = p,
-                _ => {}
-            }
-            grid.insert(p, shape);
-        }
-    }
-
-    let mut loop_bounds: HashSet<Point<i32>> = HashSet::new();
-    loop_bounds.insert(start);
-    let mut queue: Vec<Point<i32>> = vec![start];
-    while !queue.is_empty() {
-        let current = queue.pop().unwrap();
-        queue.append(
-            &mut get_neighbors(&grid, current)
-                .into_iter()
-                .filter(|x| !loop_bounds.contains(x))
-                .collect::<Vec<Point<i32>>>(),
-        );
-
-        loop_bounds.insert(current);
-    }
-
-    grid.entry(start).and_modify(|e| {
-        *e = match (
-            loop_bounds.contains(&north(start)),
-            loop_bounds.contains(&east(start)),
-            loop_bounds.contains(&south(start)),
-            loop_bounds.contains(&west(start)),
-        ) {
-            (true, true, false, false) => 'L',
-            (true, false, true, false) => '|',
-            (true, false, false, true) => 'J',
-            (false, true, true, false) => 'F',
-            (false, true, false, true) => '-',
-            (false, false, true, true) => '7',
-            _ => 'S',
-        };
-    });
-
-    // Remove the non-loop points.
-    for r in 0..height {
-        for c in 0..width {
-            let p = Point { x: c, y: r };
-            if !loop_bounds.contains(&p) {
-                if let Some(shape) = grid.get(&p) {
-                    if *shape != '.' {
-                        grid.entry(p).and_modify(|e| *e = '.');
-                    }
-                }
-            }
-        }
-    }
-
-    let mut inside_points: HashSet<Point<i32>> = HashSet::new();
-    let mut inside_count = 0;
-    for r in 0..height {
-        let mut cross = 0;
-        println!("reset");
-        for c in 0..width {
-            let p = Point { x: c, y: r };
-            if loop_bounds.contains(&p) {
-                // Only the pipes that have an edge going up.
-                if vec!['|', 'J', 'L'].contains(grid.get(&p).unwrap()) {
-                    println!("going right, crossed {} at {}", grid.get(&p).unwrap(), p);
-                    cross += 1;
-                }
-                continue;
-            }
-
-            if cross % 2 == 1 {
-                println!("inserting {}", p);
-                inside_points.insert(p);
-                inside_count += 1;
-            }
-        }
-    }
-
-    println!("CHECK ANSWER");
-    for r in (0..height).rev() {
-        for c in 0..width {
-            let p = Point { x: c, y: r };
-            if loop_bounds.contains(&p) {
-                print!("{}", grid.get(&p).unwrap());
-            } else if inside_points.contains(&p) {
-                print!("I");
-            } else {
-                print!(".");
-            }
-        }
-        println!("");
-    }
-
-    inside_count
+    geometry::interior_points(&pipes) as i32
 }
 
 #[cfg(test)]
@@ -346,7 +184,8 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt1(&input), want, "for input\n{}", input.join("\n"));
+            let field = Field::parse(&input).unwrap();
+            assert_eq!(handle_pt1(&field), want, "for input\n{}", input.join("\n"));
         }
     }
 
@@ -441,7 +280,25 @@ mod tests {
         ];
 
         for (input, want) in tests {
-            assert_eq!(handle_pt2(&input), want, "for input\n{}", input.join("\n"));
+            let field = Field::parse(&input).unwrap();
+            assert_eq!(handle_pt2(&field), want, "for input\n{}", input.join("\n"));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec![".....", ".F-7.", ".|.|.", ".L-J."], 4),
+            (vec![".S-7.", ".|.|.", ".L-X."], 3),
+            (vec![".S-7.", ".|.|.", ".L-S."], 3),
+            (vec![".....", ".S-7.", "...|.", ".L-J."], 2),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Field::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
         }
     }
 }
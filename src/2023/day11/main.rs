@@ -1,24 +1,34 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_range_loop, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    let pt1: usize = handle_pt1(&lines, 1);
-    println!("Part 1: {}", pt1);
-    // 717878975886 too high
-    let pt2: usize = handle_pt1(&lines, 999_999);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        handle_pt1(lines, 1)
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        handle_pt1(lines, 999_999)
+    }
 }
 
 fn handle_pt1(lines: &Vec<String>, increase: usize) -> usize {
@@ -35,13 +45,11 @@ fn handle_pt1(lines: &Vec<String>, increase: usize) -> usize {
 
     let height = lines.len();
     let empty_rows = (0..height)
-        .into_iter()
         .filter(|row| galaxies.iter().all(|galaxy| galaxy.y != *row))
         .collect::<Vec<usize>>();
 
     let width = lines.iter().map(|line| line.len()).max().unwrap();
     let empty_cols = (0..width)
-        .into_iter()
         .filter(|col| galaxies.iter().all(|galaxy| galaxy.x != *col))
         .collect::<Vec<usize>>();
 
@@ -109,8 +117,8 @@ fn handle_pt1(lines: &Vec<String>, increase: usize) -> usize {
     let mut total = 0;
     for i in 0..galaxies.len() {
         for j in i + 1..galaxies.len() {
-            total += ((galaxies[i].x as i32 - galaxies[j].x as i32).abs() as usize)
-                + ((galaxies[i].y as i32 - galaxies[j].y as i32).abs() as usize);
+            total += ((galaxies[i].x as i32 - galaxies[j].x as i32).unsigned_abs() as usize)
+                + ((galaxies[i].y as i32 - galaxies[j].y as i32).unsigned_abs() as usize);
         }
     }
 
@@ -1,33 +1,72 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use std::collections::HashMap;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::len_zero, clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::memo::Memo;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    let pt1: usize = handle_pt1(&lines);
-    // 7215 too low
-    println!("Part 1: {}", pt1);
-    let pt2: usize = handle_pt2(&lines);
-    // 295687560777 too low
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
+
+    type Input = Vec<Record>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Record::parse(i + 1, line))
+            .collect()
+    }
+
+    fn part1(records: &Self::Input) -> usize {
+        handle_pt1(records)
+    }
+
+    fn part2(records: &Self::Input) -> usize {
+        handle_pt2(records)
+    }
 }
 
-fn handle_pt1(lines: &Vec<String>) -> usize {
-    lines.iter().map(|line| count_possibilities(line)).sum()
+#[derive(Debug, Clone)]
+pub struct Record {
+    gears: Vec<char>,
+    config: Vec<usize>,
 }
 
-fn handle_pt2(lines: &Vec<String>) -> usize {
-    lines.iter().map(|line| count_5x_possibilities(line)).sum()
+impl Record {
+    pub fn parse(line_no: usize, s: &str) -> Result<Self, AocError> {
+        let (gears, config) = s.split_once(' ').ok_or_else(|| {
+            AocError::parse(line_no, format!("expected gears and groups: {:?}", s))
+        })?;
+        if let Some(c) = gears.chars().find(|c| !matches!(c, '.' | '#' | '?')) {
+            return Err(AocError::parse(line_no, format!("unknown gear {:?}", c)));
+        }
+        let config = config
+            .split(',')
+            .map(|x| parse_at::<usize>(line_no, x))
+            .collect::<Result<Vec<usize>, AocError>>()?;
+        return Ok(Self {
+            gears: gears.chars().collect(),
+            config,
+        });
+    }
+}
+
+fn handle_pt1(records: &Vec<Record>) -> usize {
+    records.iter().map(count_possibilities).sum()
+}
+
+fn handle_pt2(records: &Vec<Record>) -> usize {
+    records.iter().map(count_5x_possibilities).sum()
 }
 
 fn can_break(g: &char) -> bool {
@@ -38,40 +77,17 @@ fn can_operate(g: &char) -> bool {
     *g == '.' || *g == '?'
 }
 
-fn memo_key(gears: &Vec<char>, config: &Vec<usize>) -> String {
-    format!(
-        "{} {}",
-        gears.iter().collect::<String>(),
-        config
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",")
-    )
-    .to_string()
-}
-
-fn try_memo_possibility(
-    gears: &Vec<char>,
-    config: &Vec<usize>,
-    memo: &mut HashMap<String, usize>,
-) -> usize {
-    let key = memo_key(gears, config);
-    if let Some(m) = memo.get(&key) {
-        return *m;
-    }
+/// Everything gets worked out on the ends of one line's gears and config, so
+/// how much of each is left is enough to tell them apart.
+type Cache = Memo<(usize, usize), usize>;
 
-    let m = try_possibility(gears, config, memo);
-    memo.insert(key, m);
-
-    m
+fn try_memo_possibility(gears: &[char], config: &[usize], memo: &mut Cache) -> usize {
+    memo.get_or_compute((gears.len(), config.len()), |memo| {
+        try_possibility(gears, config, memo)
+    })
 }
 
-fn try_possibility(
-    gears: &Vec<char>,
-    config: &Vec<usize>,
-    memo: &mut HashMap<String, usize>,
-) -> usize {
+fn try_possibility(gears: &[char], config: &[usize], memo: &mut Cache) -> usize {
     if gears.len() == 0 {
         let mut possibilities = 0;
         if config.len() == 0 {
@@ -83,7 +99,7 @@ fn try_possibility(
 
     if config.len() == 0 {
         let mut possibilities = 0;
-        if gears.iter().all(|x| can_operate(x)) {
+        if gears.iter().all(can_operate) {
             possibilities = 1;
         }
 
@@ -101,11 +117,8 @@ fn try_possibility(
     match (*c, *g) {
         (1, '#') => {
             if gears.len() > 1 && can_operate(&gears[1]) {
-                let possibilities = try_memo_possibility(
-                    &gears[2..gears.len()].to_vec(),
-                    &config[1..config.len()].to_vec(),
-                    memo,
-                );
+                let possibilities =
+                    try_memo_possibility(&gears[2..gears.len()], &config[1..config.len()], memo);
                 return possibilities;
             } else if gears.len() == 1 {
                 return 1;
@@ -116,75 +129,57 @@ fn try_possibility(
         (_, '#') => {
             let mut possibilities = 0;
             if *c <= gears.len()
-                && gears[0..*c].iter().all(|x| can_break(x))
+                && gears[0..*c].iter().all(can_break)
                 && (*c == gears.len() || can_operate(&gears[*c]))
             {
                 possibilities += try_memo_possibility(
-                    &gears[*vec![*c + 1, gears.len()].iter().min().unwrap()..gears.len()].to_vec(),
-                    &config[1..config.len()].to_vec(),
+                    &gears[*[*c + 1, gears.len()].iter().min().unwrap()..gears.len()],
+                    &config[1..config.len()],
                     memo,
                 );
             }
             return possibilities;
         }
-        (_, '.') => return try_memo_possibility(&gears[1..gears.len()].to_vec(), config, memo),
+        (_, '.') => return try_memo_possibility(&gears[1..gears.len()], config, memo),
         (1, '?') => {
             let mut broken = 0;
             if gears.len() == 1 || (gears.len() > 1 && can_operate(&gears[1])) {
                 broken = try_memo_possibility(
-                    &gears[*vec![2 as usize, gears.len()].iter().min().unwrap()..gears.len()]
-                        .to_vec(),
-                    &config[1..config.len()].to_vec(),
+                    &gears[*[2_usize, gears.len()].iter().min().unwrap()..gears.len()],
+                    &config[1..config.len()],
                     memo,
                 );
             }
-            return try_memo_possibility(&gears[1..gears.len()].to_vec(), config, memo) + broken;
+            return try_memo_possibility(&gears[1..gears.len()], config, memo) + broken;
         }
         (_, '?') => {
-            let operational = try_memo_possibility(&gears[1..gears.len()].to_vec(), config, memo);
+            let operational = try_memo_possibility(&gears[1..gears.len()], config, memo);
             let mut broken = 0;
             if *c <= gears.len()
-                && gears[0..*c].iter().all(|x| can_break(x))
+                && gears[0..*c].iter().all(can_break)
                 && (*c == gears.len() || can_operate(&gears[*c]))
             {
                 broken = try_memo_possibility(
-                    &gears[*vec![*c + 1, gears.len()].iter().min().unwrap()..gears.len()].to_vec(),
-                    &config[1..config.len()].to_vec(),
+                    &gears[*[*c + 1, gears.len()].iter().min().unwrap()..gears.len()],
+                    &config[1..config.len()],
                     memo,
                 );
             }
             return operational + broken;
         }
-        _ => todo!(),
+        _ => unreachable!("Record::parse only lets through '.', '#' and '?'"),
     }
 }
 
-fn count_possibilities(line: &str) -> usize {
-    let parts = line.split(" ").collect::<Vec<&str>>();
-    let gears = parts[0].chars().collect::<Vec<char>>();
-    let config = parts[1]
-        .split(",")
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect::<Vec<usize>>();
-
-    try_memo_possibility(&gears, &config, &mut HashMap::new())
+fn count_possibilities(record: &Record) -> usize {
+    try_memo_possibility(&record.gears, &record.config, &mut Memo::new())
 }
 
-fn count_5x_possibilities(line: &str) -> usize {
-    let parts = line.split(" ").collect::<Vec<&str>>();
-    let gears = vec![parts[0]; 5].join("?").chars().collect::<Vec<char>>();
-    let config = vec![
-        parts[1]
-            .split(",")
-            .map(|x| x.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
-        5
-    ]
-    .into_iter()
-    .flatten()
-    .collect::<Vec<usize>>();
-
-    try_memo_possibility(&gears, &config, &mut HashMap::new())
+fn count_5x_possibilities(record: &Record) -> usize {
+    let gears = [record.gears.as_slice(); 5].join(&'?');
+    let config = record.config.repeat(5);
+
+    try_memo_possibility(&gears, &config, &mut Memo::new())
 }
 
 #[cfg(test)]
@@ -210,13 +205,17 @@ mod tests {
             (String::from("?????#?#?? 3,5"), 3),
             (String::from("?.???????###.????? 1,2,2,4,3"), 3),
             (String::from("#?#???.??#?? 4,4"), 2),
-            (String::from(".????#?????.?????.? 8,3"), 4),
+            // The 8 has to cover the `#`, so starts at 1, 2 or 3. The 3 can't
+            // fit after it in the same block, so goes in one of 3 places in
+            // the second block.
+            (String::from(".????#?????.?????.? 8,3"), 9),
             (String::from("??#??#?????.?????? 7,5"), 6),
             (String::from("#??#??#???#..??? 1,5,2,1"), 6),
         ];
 
         for (input, want) in tests {
-            assert_eq!(count_possibilities(&input), want, "with input\n{}", input);
+            let record = Record::parse(1, &input).unwrap();
+            assert_eq!(count_possibilities(&record), want, "with input\n{}", input);
         }
     }
 
@@ -232,12 +231,29 @@ mod tests {
         ];
 
         for (input, want) in tests {
+            let record = Record::parse(1, &input).unwrap();
             assert_eq!(
-                count_5x_possibilities(&input),
+                count_5x_possibilities(&record),
                 want,
                 "with input\n{}",
                 input
             );
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec!["???.### 1,1,3", "???.###"], 2),
+            (vec!["???.### 1,x,3"], 1),
+            (vec!["???.### 1,1,3", ".# 1", "?a? 1"], 3),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Day::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
+        }
+    }
 }
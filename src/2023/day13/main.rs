@@ -1,28 +1,42 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
+#![allow(clippy::needless_range_loop, clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::parse::sections;
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    let (pt1, pt2): (usize, usize) = handle(&lines);
-    // 34893 is low
-    // 34993
-    println!("Part 1: {}", pt1);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 13;
+
+    /// Both parts come out of the same pass over the patterns.
+    type Input = (usize, usize);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(handle(&lines.to_vec()))
+    }
+
+    fn part1(&(pt1, _): &Self::Input) -> usize {
+        // 34893 is low
+        // 34993
+        pt1
+    }
+
+    fn part2(&(_, pt2): &Self::Input) -> usize {
+        pt2
+    }
 }
 
 fn handle(lines: &Vec<String>) -> (usize, usize) {
     let (part1, part2): (Vec<usize>, Vec<usize>) =
-        split_input(lines).iter().map(|l| reflection(l)).unzip();
+        sections(lines).into_iter().map(reflection).unzip();
     (part1.iter().sum(), part2.iter().sum())
 }
 
@@ -39,11 +53,11 @@ fn diff(a: &str, b: &str) -> usize {
     diffs
 }
 
-fn find_smudged_reflection(lines: &Vec<String>) -> usize {
+fn find_smudged_reflection(lines: &[String]) -> usize {
     for i in 1..lines.len() {
         let mut matches = true;
         let mut diffs = 0;
-        for r in 0..*(vec![i, lines.len() - i].iter().min().unwrap()) {
+        for r in 0..*([i, lines.len() - i].iter().min().unwrap()) {
             diffs += diff(&lines[i + r], &lines[i - r - 1]);
             if diffs > 1 {
                 matches = false;
@@ -58,10 +72,10 @@ fn find_smudged_reflection(lines: &Vec<String>) -> usize {
     0
 }
 
-fn find_reflection(lines: &Vec<String>) -> usize {
+fn find_reflection(lines: &[String]) -> usize {
     for i in 1..lines.len() {
         let mut matches = true;
-        for r in 0..*(vec![i, lines.len() - i].iter().min().unwrap()) {
+        for r in 0..*([i, lines.len() - i].iter().min().unwrap()) {
             if lines[i + r] != lines[i - r - 1] {
                 matches = false;
                 break;
@@ -75,7 +89,7 @@ fn find_reflection(lines: &Vec<String>) -> usize {
     return 0;
 }
 
-fn reflection(lines: &Vec<String>) -> (usize, usize) {
+fn reflection(lines: &[String]) -> (usize, usize) {
     let transposed = transpose(lines);
 
     let horizontal_pt1 = find_reflection(lines) * 100;
@@ -107,7 +121,7 @@ fn reflection(lines: &Vec<String>) -> (usize, usize) {
     (pt1, pt2)
 }
 
-fn transpose(lines: &Vec<String>) -> Vec<String> {
+fn transpose(lines: &[String]) -> Vec<String> {
     let height = lines.len();
     let width = lines.iter().map(|line| line.len()).max().unwrap();
 
@@ -124,26 +138,6 @@ fn transpose(lines: &Vec<String>) -> Vec<String> {
     transpose
 }
 
-fn split_input(lines: &Vec<String>) -> Vec<Vec<String>> {
-    let mut res: Vec<Vec<String>> = Vec::new();
-    let mut curr: Vec<String> = Vec::new();
-    for line in lines {
-        if line.is_empty() {
-            res.push(curr);
-            curr = Vec::new();
-            continue;
-        }
-
-        curr.push(line.clone());
-    }
-
-    if !curr.is_empty() {
-        res.push(curr);
-    }
-
-    res
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
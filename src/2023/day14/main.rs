@@ -1,41 +1,50 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::cycle::{self, Cycle};
 use advent_of_code::utils::point::Point;
-use clap::Parser;
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
-
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-
-    let pt1: usize = handle_pt1(&lines);
-    // 108518 not right
-    println!("Part 1: {}", pt1);
-    let pt2: usize = handle_pt2(&lines);
-    println!("Part 2: {}", pt2);
+    advent_of_code::solution::run::<Day>();
 }
 
-fn handle_pt1(lines: &Vec<String>) -> usize {
-    let height = lines.len();
-    let width = lines.iter().map(|line| line.len()).max().unwrap();
-    let mut grid: HashMap<Point<usize>, char> = HashMap::new();
+pub struct Day;
 
-    for (row, line) in lines.iter().enumerate() {
-        for (col, shape) in line.chars().enumerate() {
-            let p = Point {
-                x: col + 1 as usize,
-                y: lines.len() as usize - row as usize,
-            };
-            grid.insert(p, shape);
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 14;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        // 108518 not right
+        handle_pt1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        handle_pt2(lines)
+    }
+
+    fn visualize(lines: &Self::Input, viz: &mut Visualizer) {
+        let (grid, width, height) = parse_dish(lines);
+        for state in spins(&grid, width, height).states {
+            let grid = parse_grid_string(&state, width);
+            viz.frame(&draw_grid(&grid, width, height));
         }
     }
+}
+
+fn handle_pt1(lines: &Vec<String>) -> usize {
+    let (mut grid, width, height) = parse_dish(lines);
 
     let mut total = 0;
     for w in 1..=width {
@@ -75,18 +84,20 @@ fn grid_string(grid: &HashMap<Point<usize>, char>, width: usize, height: usize)
     res.iter().collect::<String>()
 }
 
-fn show_grid(grid: &HashMap<Point<usize>, char>, width: usize, height: usize) {
-    let mut res: Vec<char> = Vec::new();
-    for h in (1..=height).rev() {
-        for w in 1..=width {
-            let p = Point { x: w, y: h };
-            res.push(*grid.get(&p).unwrap());
+/// The dish the way it looks in the input, with north at the top.
+fn draw_grid(grid: &HashMap<Point<usize>, char>, width: usize, height: usize) -> Frame {
+    let corner = Point::new(width as i64, height as i64);
+    return viz::draw(Point::new(1, 1), corner, |p| {
+        let p = Point {
+            x: p.x as usize,
+            y: height + 1 - p.y as usize,
+        };
+        match grid.get(&p) {
+            Some('O') => Cell::colored('O', Rgb(220, 140, 60)),
+            Some('#') => Cell::colored('#', Rgb(130, 130, 140)),
+            _ => Cell::from('.'),
         }
-
-        res.push('\n');
-    }
-
-    println!("{}", res.iter().collect::<String>());
+    });
 }
 
 fn parse_grid_string(s: &str, width: usize) -> HashMap<Point<usize>, char> {
@@ -100,143 +111,124 @@ fn parse_grid_string(s: &str, width: usize) -> HashMap<Point<usize>, char> {
     grid
 }
 
-fn handle_pt2(lines: &Vec<String>) -> usize {
-    let height = lines.len();
-    let width = lines.iter().map(|line| line.len()).max().unwrap();
-    let mut grid: HashMap<Point<usize>, char> = HashMap::new();
-
-    let mut memo: HashMap<String, i32> = HashMap::new();
-    let mut rev_memo: HashMap<i32, String> = HashMap::new();
-
-    for (row, line) in lines.iter().enumerate() {
-        for (col, shape) in line.chars().enumerate() {
-            let p = Point {
-                x: col + 1 as usize,
-                y: lines.len() as usize - row as usize,
-            };
-            grid.insert(p, shape);
+fn spin(grid: &mut HashMap<Point<usize>, char>, width: usize, height: usize) {
+    // North
+    for w in 1..=width {
+        let mut next = height;
+        for h in (1..=height).rev() {
+            let p = Point { x: w, y: h };
+            match grid.get(&p) {
+                Some('.') => {}
+                Some('O') => {
+                    let new_p = Point { x: w, y: next };
+                    grid.entry(p).and_modify(|e| *e = '.');
+                    grid.entry(new_p).and_modify(|e| *e = 'O');
+                    next -= 1;
+                }
+                Some('#') => next = h - 1,
+                _ => todo!(),
+            }
         }
     }
 
-    println!("START");
-    show_grid(&grid, width, height);
-    let total_cycles = 1000000000;
-    let mut cycle = 0;
-    let mut key: String = String::from("");
-    while cycle < total_cycles {
-        // North
+    // West
+    for h in 1..=height {
+        let mut next = 1;
         for w in 1..=width {
-            let mut next = height;
-            for h in (1..=height).rev() {
-                let p = Point { x: w, y: h };
-                match grid.get(&p) {
-                    Some('.') => {}
-                    Some('O') => {
-                        let new_p = Point { x: w, y: next };
-                        grid.entry(p).and_modify(|e| *e = '.');
-                        grid.entry(new_p).and_modify(|e| *e = 'O');
-                        next -= 1;
-                    }
-                    Some('#') => next = h - 1,
-                    _ => todo!(),
+            let p = Point { x: w, y: h };
+            match grid.get(&p) {
+                Some('.') => {}
+                Some('O') => {
+                    let new_p = Point { x: next, y: h };
+                    grid.entry(p).and_modify(|e| *e = '.');
+                    grid.entry(new_p).and_modify(|e| *e = 'O');
+                    next += 1;
                 }
+                Some('#') => next = w + 1,
+                _ => todo!(),
             }
         }
-        //   println!("NORTH");
-        //   show_grid(&grid, width, height);
+    }
 
-        // West
+    // South
+    for w in 1..=width {
+        let mut next = 1;
         for h in 1..=height {
-            let mut next = 1;
-            for w in 1..=width {
-                let p = Point { x: w, y: h };
-                match grid.get(&p) {
-                    Some('.') => {}
-                    Some('O') => {
-                        let new_p = Point { x: next, y: h };
-                        grid.entry(p).and_modify(|e| *e = '.');
-                        grid.entry(new_p).and_modify(|e| *e = 'O');
-                        next += 1;
-                    }
-                    Some('#') => next = w + 1,
-                    _ => todo!(),
+            let p = Point { x: w, y: h };
+            match grid.get(&p) {
+                Some('.') => {}
+                Some('O') => {
+                    let new_p = Point { x: w, y: next };
+                    grid.entry(p).and_modify(|e| *e = '.');
+                    grid.entry(new_p).and_modify(|e| *e = 'O');
+                    next += 1;
                 }
+                Some('#') => next = h + 1,
+                _ => todo!(),
             }
         }
-        //   println!("WEST");
-        //   show_grid(&grid, width, height);
+    }
 
-        // South
-        for w in 1..=width {
-            let mut next = 1;
-            for h in 1..=height {
-                let p = Point { x: w, y: h };
-                match grid.get(&p) {
-                    Some('.') => {}
-                    Some('O') => {
-                        let new_p = Point { x: w, y: next };
-                        grid.entry(p).and_modify(|e| *e = '.');
-                        grid.entry(new_p).and_modify(|e| *e = 'O');
-                        next += 1;
-                    }
-                    Some('#') => next = h + 1,
-                    _ => todo!(),
+    // East
+    for h in 1..=height {
+        let mut next = width;
+        for w in (1..=width).rev() {
+            let p = Point { x: w, y: h };
+            match grid.get(&p) {
+                Some('.') => {}
+                Some('O') => {
+                    let new_p = Point { x: next, y: h };
+                    grid.entry(p).and_modify(|e| *e = '.');
+                    grid.entry(new_p).and_modify(|e| *e = 'O');
+                    next -= 1;
                 }
+                Some('#') => next = w - 1,
+                _ => todo!(),
             }
         }
-        //   println!("SOUTH");
-        //   show_grid(&grid, width, height);
+    }
+}
 
-        // East
-        for h in 1..=height {
-            let mut next = width;
-            for w in (1..=width).rev() {
-                let p = Point { x: w, y: h };
-                match grid.get(&p) {
-                    Some('.') => {}
-                    Some('O') => {
-                        let new_p = Point { x: next, y: h };
-                        grid.entry(p).and_modify(|e| *e = '.');
-                        grid.entry(new_p).and_modify(|e| *e = 'O');
-                        next -= 1;
-                    }
-                    Some('#') => next = w - 1,
-                    _ => todo!(),
-                }
-            }
-        }
-        //   println!("CYCLE {}", cycle);
-        //   show_grid(&grid, width, height);
-
-        key = grid_string(&grid, width, height);
-        if let Some(past_cycle) = memo.get(&key) {
-            let period = cycle - past_cycle;
-            let final_cycle = past_cycle + ((total_cycles - cycle) % period) - 1;
-            // println!(
-            //     "currently {}, past cycle = {}, with period = {}, ending up on = {}",
-            //     cycle, past_cycle, period, final_cycle
-            // );
-            grid = parse_grid_string(rev_memo.get(&final_cycle).unwrap(), width);
-            break;
-        }
+/// The dish with `x` counting columns from 1 at the west and `y` counting rows
+/// from 1 at the south, along with its width and height.
+fn parse_dish(lines: &Vec<String>) -> (HashMap<Point<usize>, char>, usize, usize) {
+    let height = lines.len();
+    let width = lines.iter().map(|line| line.len()).max().unwrap();
+    let mut grid: HashMap<Point<usize>, char> = HashMap::new();
 
-        //   println!("inserting {} for cycle {}", &key, cycle);
-        memo.insert(key.clone(), cycle);
-        rev_memo.insert(cycle, key.clone());
-        cycle += 1;
+    for (row, line) in lines.iter().enumerate() {
+        for (col, shape) in line.chars().enumerate() {
+            let p = Point {
+                x: col + 1_usize,
+                y: lines.len() - row,
+            };
+            grid.insert(p, shape);
+        }
     }
 
-    //  println!("FINAL");
-    //  show_grid(&grid, width, height);
+    (grid, width, height)
+}
+
+/// Spin cycles until the rocks end up somewhere they've been before.
+fn spins(grid: &HashMap<Point<usize>, char>, width: usize, height: usize) -> Cycle<String> {
+    cycle::find(grid_string(grid, width, height), |key| {
+        let mut grid = parse_grid_string(key, width);
+        spin(&mut grid, width, height);
+        grid_string(&grid, width, height)
+    })
+}
+
+fn handle_pt2(lines: &Vec<String>) -> usize {
+    let (grid, width, height) = parse_dish(lines);
+    let spins = spins(&grid, width, height);
+    let grid = parse_grid_string(spins.state_at(1000000000), width);
+
     let mut total = 0;
     for h in 1..=height {
         for w in 1..=width {
             let p = Point { x: w, y: h };
-            match grid.get(&p) {
-                Some('O') => {
-                    total += h;
-                }
-                _ => {}
+            if let Some('O') = grid.get(&p) {
+                total += h;
             }
         }
     }
@@ -1,24 +1,34 @@
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
+#![allow(clippy::assertions_on_constants, clippy::needless_return)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
 use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-    let codes = lines.into_iter().nth(0).unwrap();
-
-    let pt1: usize = handle_pt1(&codes);
-    println!("Part 1: {}", pt1);
-    let pt2: usize = handle_pt2(&codes);
-    println!("Part 2: {}", pt2);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 15;
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.first().unwrap().clone())
+    }
+
+    fn part1(codes: &Self::Input) -> usize {
+        handle_pt1(codes)
+    }
+
+    fn part2(codes: &Self::Input) -> usize {
+        handle_pt2(codes)
+    }
 }
 
 /*
@@ -39,7 +49,7 @@ fn hash(code: &str) -> usize {
 }
 
 fn handle_pt1(codes: &str) -> usize {
-    codes.split(",").into_iter().map(|c| hash(c)).sum()
+    codes.split(",").map(hash).sum()
 }
 
 fn parse_code(code: &str) -> (String, char, Option<usize>) {
@@ -64,6 +74,12 @@ pub struct LensBox {
     next_index: usize,
 }
 
+impl Default for LensBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LensBox {
     pub fn new() -> Self {
         Self {
@@ -105,7 +121,7 @@ impl LensBox {
     // changing their order, filling any space made by removing the indicated
     // lens. (If no lens in that box has the given label, nothing happens.)
     pub fn remove(&mut self, label: &str) {
-        if self.indices.get(label).is_none() {
+        if !self.indices.contains_key(label) {
             return;
         }
 
@@ -1,51 +1,37 @@
-use advent_of_code::utils::input::read_lines;
-use advent_of_code::utils::point::Point;
-use clap::Parser;
+#![allow(clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::memo::Memo;
+use advent_of_code::utils::point::{Direction, Point};
 use std::collections::{HashMap, HashSet};
+use Direction::*;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-    let (grid, (width, height)) = create_grid(&lines);
+pub struct Day;
 
-    println!("Part 1: {}", handle_pt1(&grid, width, height));
-    println!("Part 2: {}", handle_pt2(&grid, width, height));
-}
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 16;
+
+    type Input = (HashMap<Point<i32>, char>, (i32, i32));
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(create_grid(&lines.to_vec()))
+    }
 
-const WEST: char = 'W';
-const EAST: char = 'E';
-const NORTH: char = 'N';
-const SOUTH: char = 'S';
-
-fn go(p: Point<i32>, dir: char, width: i32, height: i32) -> Option<(Point<i32>, char)> {
-    let new_point = Point {
-        x: p.x
-            + match dir {
-                EAST => 1,
-                WEST => -1,
-                _ => 0,
-            },
-        y: p.y
-            + match dir {
-                NORTH => 1,
-                SOUTH => -1,
-                _ => 0,
-            },
-    };
-
-    if new_point.x < 0 || new_point.x >= width || new_point.y < 0 || new_point.y >= height {
-        return None;
+    fn part1((grid, (width, height)): &Self::Input) -> i32 {
+        handle_pt1(grid, *width, *height)
     }
 
-    Some((new_point, dir))
+    fn part2((grid, (width, height)): &Self::Input) -> i32 {
+        handle_pt2(grid, *width, *height)
+    }
 }
 
 fn create_grid(lines: &Vec<String>) -> (HashMap<Point<i32>, char>, (i32, i32)) {
@@ -57,7 +43,7 @@ fn create_grid(lines: &Vec<String>) -> (HashMap<Point<i32>, char>, (i32, i32)) {
         for (col, shape) in line.chars().enumerate() {
             let p = Point {
                 x: col as i32,
-                y: lines.len() as i32 - row as i32 - 1,
+                y: row as i32,
             };
             grid.insert(p, shape);
         }
@@ -66,19 +52,20 @@ fn create_grid(lines: &Vec<String>) -> (HashMap<Point<i32>, char>, (i32, i32)) {
     (grid, (width, height))
 }
 
+/// Where a beam goes next from each place and direction already worked out.
+type Beams = Memo<(Point<i32>, Direction), Vec<(Point<i32>, Direction)>>;
+
 fn grab_from_memo(
-    check: (Point<i32>, char),
-    memo: &HashMap<(Point<i32>, char), Vec<(Point<i32>, char)>>,
-    visited: &mut HashSet<(Point<i32>, char)>,
+    check: (Point<i32>, Direction),
+    memo: &mut Beams,
+    visited: &mut HashSet<(Point<i32>, Direction)>,
     lights: &mut HashSet<Point<i32>>,
 ) -> bool {
     let mut found = false;
 
-    let mut stack = Vec::new();
-    stack.push(check);
+    let mut stack = vec![check];
 
-    while !stack.is_empty() {
-        let curr = stack.pop().unwrap();
+    while let Some(curr) = stack.pop() {
         if visited.contains(&curr) {
             continue;
         }
@@ -101,19 +88,18 @@ fn shoot_beams(
     width: i32,
     height: i32,
     start: Point<i32>,
-    start_dir: char,
-    memo: &mut HashMap<(Point<i32>, char), Vec<(Point<i32>, char)>>,
+    start_dir: Direction,
+    memo: &mut Beams,
 ) -> i32 {
-    let mut visited: HashSet<(Point<i32>, char)> = HashSet::new();
+    let mut visited: HashSet<(Point<i32>, Direction)> = HashSet::new();
 
     let mut lights: HashSet<Point<i32>> = HashSet::new();
     lights.insert(start);
 
-    let mut beams: Vec<(Point<i32>, char)> = Vec::new();
-    beams.push((start, start_dir));
+    let mut beams: Vec<(Point<i32>, Direction)> = vec![(start, start_dir)];
 
     while !beams.is_empty() {
-        let mut next_beams: Vec<(Point<i32>, char)> = Vec::new();
+        let mut next_beams: Vec<(Point<i32>, Direction)> = Vec::new();
         for (loc, dir) in beams {
             if visited.contains(&(loc, dir)) {
                 continue;
@@ -123,33 +109,24 @@ fn shoot_beams(
                 continue;
             }
 
-            let mut next = match (grid.get(&loc).unwrap(), dir) {
-                ('-', NORTH) => {
-                    vec![go(loc, WEST, width, height), go(loc, EAST, width, height)]
-                }
-                ('-', SOUTH) => {
-                    vec![go(loc, WEST, width, height), go(loc, EAST, width, height)]
-                }
-                ('|', EAST) => {
-                    vec![go(loc, NORTH, width, height), go(loc, SOUTH, width, height)]
-                }
-                ('|', WEST) => {
-                    vec![go(loc, NORTH, width, height), go(loc, SOUTH, width, height)]
-                }
-                ('/', NORTH) => vec![go(loc, EAST, width, height)],
-                ('/', EAST) => vec![go(loc, NORTH, width, height)],
-                ('/', WEST) => vec![go(loc, SOUTH, width, height)],
-                ('/', SOUTH) => vec![go(loc, WEST, width, height)],
-                ('\\', NORTH) => vec![go(loc, WEST, width, height)],
-                ('\\', EAST) => vec![go(loc, SOUTH, width, height)],
-                ('\\', WEST) => vec![go(loc, NORTH, width, height)],
-                ('\\', SOUTH) => vec![go(loc, EAST, width, height)],
-                _ => vec![go(loc, dir, width, height)],
-            }
-            .iter()
-            .filter(|x| x.is_some())
-            .map(|x| x.unwrap())
-            .collect::<Vec<(Point<i32>, char)>>();
+            let turns = match (grid.get(&loc).unwrap(), dir) {
+                ('-', North | South) => vec![West, East],
+                ('|', East | West) => vec![North, South],
+                ('/', North) => vec![East],
+                ('/', East) => vec![North],
+                ('/', West) => vec![South],
+                ('/', South) => vec![West],
+                ('\\', North) => vec![West],
+                ('\\', East) => vec![South],
+                ('\\', West) => vec![North],
+                ('\\', South) => vec![East],
+                _ => vec![dir],
+            };
+            let mut next = turns
+                .into_iter()
+                .map(|dir| (loc.step(dir), dir))
+                .filter(|(p, _)| p.x >= 0 && p.x < width && p.y >= 0 && p.y < height)
+                .collect::<Vec<(Point<i32>, Direction)>>();
 
             memo.insert((loc, dir), next.clone());
 
@@ -162,10 +139,8 @@ fn shoot_beams(
     }
 
     (0..width)
-        .into_iter()
         .map(|w| {
             (0..height)
-                .into_iter()
                 .map(|h| lights.contains(&Point { x: w, y: h }))
                 .filter(|x| *x)
                 .count()
@@ -180,53 +155,24 @@ fn handle_pt1(grid: &HashMap<Point<i32>, char>, width: i32, height: i32) -> i32
         grid,
         width,
         height,
-        Point {
-            x: 0,
-            y: height - 1,
-        },
-        EAST,
-        &mut HashMap::new(),
+        Point { x: 0, y: 0 },
+        East,
+        &mut Memo::new(),
     )
 }
 
 fn handle_pt2(grid: &HashMap<Point<i32>, char>, width: i32, height: i32) -> i32 {
-    let mut starts: Vec<(Point<i32>, char)> = Vec::new();
-    for h in 0..height {
-        if h == 0 {
-            starts.append(&mut vec![
-                (Point { x: 0, y: h }, EAST),
-                (Point { x: 0, y: h }, SOUTH),
-                (Point { x: width - 1, y: h }, WEST),
-                (Point { x: width - 1, y: h }, SOUTH),
-            ]);
-        } else if h == height - 1 {
-            starts.append(&mut vec![
-                (Point { x: 0, y: h }, EAST),
-                (Point { x: 0, y: h }, SOUTH),
-                (Point { x: width - 1, y: h }, WEST),
-                (Point { x: width - 1, y: h }, NORTH),
-            ]);
-        } else {
-            starts.append(&mut vec![
-                (Point { x: 0, y: h }, EAST),
-                (Point { x: width - 1, y: h }, WEST),
-            ]);
-        }
+    let mut starts: Vec<(Point<i32>, Direction)> = Vec::new();
+    for y in 0..height {
+        starts.push((Point { x: 0, y }, East));
+        starts.push((Point { x: width - 1, y }, West));
     }
-    for w in 1..width - 1 {
-        starts.append(&mut vec![
-            (Point { x: w, y: 0 }, NORTH),
-            (
-                Point {
-                    x: w,
-                    y: height - 1,
-                },
-                SOUTH,
-            ),
-        ]);
+    for x in 0..width {
+        starts.push((Point { x, y: 0 }, South));
+        starts.push((Point { x, y: height - 1 }, North));
     }
 
-    let mut memo = HashMap::new();
+    let mut memo = Memo::new();
     starts
         .iter()
         .map(|(start_loc, start_dir)| {
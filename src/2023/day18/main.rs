@@ -1,33 +1,47 @@
-use advent_of_code::utils::input::read_lines;
-use advent_of_code::utils::point::Point;
-use clap::Parser;
+#![allow(clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::geometry;
+use advent_of_code::utils::point::{Direction, Point};
 use std::fmt;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
 
-    println!("Part 1: {}", handle_pt1(&lines));
-    println!("Part 2: {}", handle_pt2(&lines));
-}
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 18;
+
+    type Input = Vec<(Dig, Dig)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(i + 1, line))
+            .collect()
+    }
+
+    fn part1(plan: &Self::Input) -> i64 {
+        handle_pt1(plan)
+    }
 
-const UP: char = 'U';
-const DOWN: char = 'D';
-const LEFT: char = 'L';
-const RIGHT: char = 'R';
+    fn part2(plan: &Self::Input) -> i64 {
+        handle_pt2(plan)
+    }
+}
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
 pub struct Dig {
     steps: i64,
-    dir: char,
+    dir: Direction,
 }
 
 impl fmt::Display for Dig {
@@ -36,114 +50,69 @@ impl fmt::Display for Dig {
     }
 }
 
-fn parse_line_pt1(s: &str) -> Dig {
-    let mut dig = Dig { steps: 0, dir: '?' };
-    for (i, part) in s.split(' ').enumerate() {
-        match i {
-            0 => dig.dir = *part.chars().collect::<Vec<char>>().first().unwrap(),
-            1 => dig.steps = part.parse::<i64>().unwrap(),
-            _ => {}
-        }
-    }
-    dig
-}
-
-fn parse_line_pt2(s: &str) -> Dig {
-    let mut dig = Dig { steps: 0, dir: '?' };
-    for (i, part) in s.split(' ').enumerate() {
-        match i {
-            2 => {
-                let stripped = part.strip_prefix("(#").unwrap().strip_suffix(")").unwrap();
-                let last_char = stripped.chars().last().unwrap();
-                dig.dir = match last_char {
-                    '0' => RIGHT,
-                    '1' => DOWN,
-                    '2' => LEFT,
-                    '3' => UP,
-                    _ => unreachable!(),
-                };
-                dig.steps =
-                    i64::from_str_radix(stripped.strip_suffix(last_char).unwrap(), 16).unwrap();
-            }
-            _ => {}
+/// Each line of the plan is the dig part 1 reads off the front, and the one
+/// part 2 reads out of the colour.
+fn parse_line(line_no: usize, s: &str) -> Result<(Dig, Dig), AocError> {
+    let (dir, steps, colour) = scan!(line_no, s, "{} {} (#{})" => char, i64, String)?;
+    let dir = match dir {
+        'R' => Direction::East,
+        'D' => Direction::South,
+        'L' => Direction::West,
+        'U' => Direction::North,
+        _ => {
+            return Err(AocError::parse(
+                line_no,
+                format!("unknown direction {:?}", dir),
+            ))
         }
-    }
-    dig
-}
+    };
 
-fn jump(p: Point<i64>, dir: char, steps: i64) -> Point<i64> {
-    Point {
-        x: p.x
-            + match dir {
-                RIGHT => steps,
-                LEFT => -steps,
-                _ => 0,
-            },
-        y: p.y
-            + match dir {
-                UP => steps,
-                DOWN => -steps,
-                _ => 0,
-            },
+    let bad_colour = || AocError::parse(line_no, format!("{:?} isn't a colour", colour));
+    if colour.len() != 6 || !colour.is_ascii() {
+        return Err(bad_colour());
     }
+    let (distance, last) = colour.split_at(5);
+    let big_dir = match last {
+        "0" => Direction::East,
+        "1" => Direction::South,
+        "2" => Direction::West,
+        "3" => Direction::North,
+        _ => return Err(bad_colour()),
+    };
+    let big_steps = i64::from_str_radix(distance, 16).map_err(|_| bad_colour())?;
+
+    Ok((
+        Dig { steps, dir },
+        Dig {
+            steps: big_steps,
+            dir: big_dir,
+        },
+    ))
 }
 
-fn handle_pt1(lines: &Vec<String>) -> i64 {
-    let digs = lines
-        .iter()
-        .map(|l| parse_line_pt1(&l))
-        .collect::<Vec<Dig>>();
-
-    shoelace(&digs)
-}
-
-fn determinant(a: Point<i64>, b: Point<i64>) -> i64 {
-    a.x * b.y - b.x * a.y
-}
+fn handle_pt1(plan: &Vec<(Dig, Dig)>) -> i64 {
+    let digs = plan.iter().map(|(dig, _)| *dig).collect::<Vec<Dig>>();
 
-fn is_counter_clockwise(digs: &Vec<Dig>) -> bool {
-    digs.iter().nth(1).unwrap().dir
-        == match digs.first().unwrap().dir {
-            RIGHT => UP,
-            UP => LEFT,
-            LEFT => DOWN,
-            DOWN => RIGHT,
-            _ => unreachable!(),
-        }
+    lagoon_size(&digs)
 }
 
-fn shoelace(digs: &Vec<Dig>) -> i64 {
-    let mut points = Vec::new();
+/// The trench goes round the middle of the cubes it digs out, so counting the
+/// grid points it goes round and through counts the cubes.
+fn lagoon_size(digs: &Vec<Dig>) -> i64 {
+    let mut corners = Vec::new();
     let mut curr: Point<i64> = Point { x: 0, y: 0 };
-    let mut surface_area = 0;
-    points.push(curr);
     for dig in digs {
-        surface_area += dig.steps;
-        curr = jump(curr, dig.dir, dig.steps);
-        points.push(curr);
-    }
-
-    if !is_counter_clockwise(digs) {
-        points.reverse();
-    }
-
-    let mut prev = *points.last().unwrap();
-    for i in 0..points.len() {
-        let curr = points[i];
-        surface_area += determinant(prev, curr);
-        prev = curr;
+        curr += dig.dir.delta() * dig.steps;
+        corners.push(curr);
     }
 
-    (surface_area.abs() / 2) + 1
+    geometry::enclosed_points(&corners)
 }
 
-fn handle_pt2(lines: &Vec<String>) -> i64 {
-    let digs = lines
-        .iter()
-        .map(|l| parse_line_pt2(&l))
-        .collect::<Vec<Dig>>();
+fn handle_pt2(plan: &Vec<(Dig, Dig)>) -> i64 {
+    let digs = plan.iter().map(|(_, dig)| *dig).collect::<Vec<Dig>>();
 
-    shoelace(&digs)
+    lagoon_size(&digs)
 }
 
 #[cfg(test)]
@@ -173,18 +142,27 @@ mod tests {
         )];
 
         for (input, (want1, want2)) in tests {
-            assert_eq!(
-                handle_pt1(&input),
-                want1,
-                "with input\n{}",
-                input.join("\n")
-            );
-            assert_eq!(
-                handle_pt2(&input),
-                want2,
-                "with input\n{}",
-                input.join("\n")
-            );
+            let plan = Day::parse(&input).unwrap();
+            assert_eq!(handle_pt1(&plan), want1, "with input\n{}", input.join("\n"));
+            assert_eq!(handle_pt2(&plan), want2, "with input\n{}", input.join("\n"));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec!["R 6 (#70c710)", "X 5 (#0dc571)"], 2),
+            (vec!["R six (#70c710)"], 1),
+            (vec!["R 6 (#70c710)", "D 5 (#0dc574)"], 2),
+            (vec!["R 6 (#70c710)", "L 2 (#5713f0)", "D 2 (#d2cg81)"], 3),
+            (vec!["R 6 #70c710"], 1),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match Day::parse(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
         }
     }
 }
@@ -1,39 +1,45 @@
-use std::collections::HashMap;
+#![allow(clippy::ptr_arg)]
 
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::error::AocError;
+use advent_of_code::scan;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::input::parse_at;
+use advent_of_code::utils::interval::Interval;
 use std::fmt;
-use std::time::Instant;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
-}
 
 fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+    advent_of_code::solution::run::<Day>();
+}
+
+pub struct Day;
 
-    let mut start = Instant::now();
-    let (checkers, values) = parse_lines(&lines);
-    println!("Part 1: {}", handle_pt1(&checkers, &values));
-    println!("Elapsed: {:.2?}", start.elapsed());
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 19;
 
-    start = Instant::now();
-    println!("Part 2: {}", handle_pt2(&checkers));
-    println!("Elapsed: {:.2?}", start.elapsed());
+    type Input = (HashMap<String, Checker>, Vec<Value>);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        parse_lines(lines)
+    }
+
+    fn part1((checkers, values): &Self::Input) -> i64 {
+        handle_pt1(checkers, values)
+    }
+
+    fn part2((checkers, _): &Self::Input) -> i64 {
+        handle_pt2(checkers)
+    }
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
 enum Operation {
     GT,
     LT,
-    LE,
-    GE,
 }
 
 pub struct Rule {
@@ -56,8 +62,6 @@ impl Rule {
         let result = match self.op {
             Operation::GT => var > self.val,
             Operation::LT => var < self.val,
-            Operation::GE => var >= self.val,
-            Operation::LE => var <= self.val,
         };
 
         if result {
@@ -77,8 +81,6 @@ impl fmt::Display for Rule {
             match self.op {
                 Operation::GT => ">",
                 Operation::LT => "<",
-                Operation::GE => ">=",
-                Operation::LE => "<=",
             },
             self.val,
             self.dst
@@ -111,99 +113,73 @@ pub struct Value {
     s: i64,
 }
 
+/// The ratings that can still get somewhere, one interval per category.
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
 pub struct ValueRange {
-    x_min: i64,
-    x_max: i64,
-    m_min: i64,
-    m_max: i64,
-    a_min: i64,
-    a_max: i64,
-    s_min: i64,
-    s_max: i64,
+    x: Interval<i64>,
+    m: Interval<i64>,
+    a: Interval<i64>,
+    s: Interval<i64>,
 }
 
 impl ValueRange {
     fn new() -> Self {
+        let all = Interval::inclusive(1, 4000);
         Self {
-            x_min: 1,
-            x_max: 4000,
-            m_min: 1,
-            m_max: 4000,
-            a_min: 1,
-            a_max: 4000,
-            s_min: 1,
-            s_max: 4000,
+            x: all,
+            m: all,
+            a: all,
+            s: all,
         }
     }
 
-    fn update(&mut self, rule: &Rule, pass: bool) {
-        let op = match (rule.op, pass) {
-            (_, true) => rule.op,
-            (Operation::GT, false) => Operation::LE,
-            (Operation::LT, false) => Operation::GE,
+    fn get(&self, var: char) -> Interval<i64> {
+        match var {
+            'x' => self.x,
+            'm' => self.m,
+            'a' => self.a,
+            's' => self.s,
             _ => unreachable!(),
-        };
-        match op {
-            Operation::GT => match rule.var {
-                'x' => self.x_min = rule.val + 1,
-                'm' => self.m_min = rule.val + 1,
-                'a' => self.a_min = rule.val + 1,
-                's' => self.s_min = rule.val + 1,
-                _ => unreachable!(),
-            },
-            Operation::LT => match rule.var {
-                'x' => self.x_max = rule.val - 1,
-                'm' => self.m_max = rule.val - 1,
-                'a' => self.a_max = rule.val - 1,
-                's' => self.s_max = rule.val - 1,
-                _ => unreachable!(),
-            },
-            Operation::LE => match rule.var {
-                'x' => self.x_max = rule.val,
-                'm' => self.m_max = rule.val,
-                'a' => self.a_max = rule.val,
-                's' => self.s_max = rule.val,
-                _ => unreachable!(),
-            },
-            Operation::GE => match rule.var {
-                'x' => self.x_min = rule.val,
-                'm' => self.m_min = rule.val,
-                'a' => self.a_min = rule.val,
-                's' => self.s_min = rule.val,
-                _ => unreachable!(),
-            },
         }
     }
 
+    fn with(&self, var: char, interval: Interval<i64>) -> Self {
+        let mut res = *self;
+        match var {
+            'x' => res.x = interval,
+            'm' => res.m = interval,
+            'a' => res.a = interval,
+            's' => res.s = interval,
+            _ => unreachable!(),
+        }
+        res
+    }
+
+    /// The ratings that pass `rule` and the ones that don't.
+    fn split(&self, rule: &Rule) -> (Option<ValueRange>, Option<ValueRange>) {
+        let passing = match rule.op {
+            Operation::GT => Interval::new(rule.val + 1, i64::MAX),
+            Operation::LT => Interval::new(i64::MIN, rule.val),
+        };
+
+        let range = self.get(rule.var);
+        let pass = range.intersect(&passing).map(|x| self.with(rule.var, x));
+        // The rule cuts at a single value, so there's at most one piece left.
+        let fail = range
+            .difference(&passing)
+            .pop()
+            .map(|x| self.with(rule.var, x));
+        (pass, fail)
+    }
+
     fn combinations(&self) -> i64 {
-        *vec![
-            (self.x_max - self.x_min + 1)
-                * (self.m_max - self.m_min + 1)
-                * (self.a_max - self.a_min + 1)
-                * (self.s_max - self.s_min + 1),
-            0,
-        ]
-        .iter()
-        .max()
-        .unwrap()
+        self.x.len() * self.m.len() * self.a.len() * self.s.len()
     }
 }
 
 impl fmt::Display for ValueRange {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "(x=[{},{}],m=[{},{}],a=[{},{}],s=[{},{}])",
-            self.x_min,
-            self.x_max,
-            self.m_min,
-            self.m_max,
-            self.a_min,
-            self.a_max,
-            self.s_min,
-            self.s_max
-        )
+        write!(f, "(x={},m={},a={},s={})", self.x, self.m, self.a, self.s)
     }
 }
 
@@ -219,123 +195,94 @@ impl fmt::Display for Value {
     }
 }
 
-fn parse_rule(line: &Vec<char>) -> Rule {
-    let mut stage = 0;
-    let mut val = Vec::new();
-    let mut dst = Vec::new();
-    let mut rule = Rule {
-        var: '?',
-        op: Operation::LT,
-        val: 0,
-        dst: String::from(""),
+/// A rule like `a<2006:qkq`.
+fn parse_rule(line_no: usize, s: &str) -> Result<Rule, AocError> {
+    let bad_rule = || AocError::parse(line_no, format!("{:?} isn't a rule", s));
+    let (condition, dst) = s.split_once(':').ok_or_else(bad_rule)?;
+    let mut chars = condition.chars();
+    let var = match chars.next() {
+        Some(c @ ('x' | 'm' | 'a' | 's')) => c,
+        _ => return Err(bad_rule()),
     };
-    for c in line {
-        match stage {
-            0 => {
-                rule.var = *c;
-                stage += 1;
-            }
-            1 => {
-                rule.op = match c {
-                    '<' => Operation::LT,
-                    '>' => Operation::GT,
-                    _ => unreachable!(),
-                };
-                stage += 1;
-            }
-            2 => {
-                if c.is_numeric() {
-                    val.push(*c);
-                } else {
-                    rule.val = val.iter().collect::<String>().parse::<i64>().unwrap();
-                    stage += 1;
-                }
-            }
-            3 => {
-                dst.push(*c);
-            }
-            _ => unreachable!(),
-        }
-    }
-
-    rule.dst = dst.iter().collect::<String>();
+    let op = match chars.next() {
+        Some('<') => Operation::LT,
+        Some('>') => Operation::GT,
+        _ => return Err(bad_rule()),
+    };
+    Ok(Rule {
+        var,
+        op,
+        val: parse_at(line_no, chars.as_str())?,
+        dst: dst.to_string(),
+    })
+}
 
-    rule
+/// A workflow like `px{a<2006:qkq,m>2090:A,rfg}`.
+fn parse_checker(line_no: usize, line: &str) -> Result<(String, Checker), AocError> {
+    let (name, rules) = scan!(line_no, line, "{}{{}}" => String, String)?;
+    let mut rules = rules.split(',').collect::<Vec<&str>>();
+    let otherwise = rules.pop().unwrap_or_default().to_string();
+    let checker = Checker {
+        rules: rules
+            .into_iter()
+            .map(|rule| parse_rule(line_no, rule))
+            .collect::<Result<Vec<Rule>, AocError>>()?,
+        otherwise,
+    };
+    Ok((name, checker))
 }
 
-fn parse_checker(line: &str) -> (String, Checker) {
-    let mut name = Vec::new();
-    let mut checker = Checker {
-        rules: Vec::new(),
-        otherwise: String::from(""),
+/// The workflows, then a blank line, then the part ratings.
+fn parse_lines(lines: &[String]) -> Result<(HashMap<String, Checker>, Vec<Value>), AocError> {
+    let Some(blank) = lines.iter().position(|line| line.is_empty()) else {
+        return Err(AocError::parse(
+            lines.len(),
+            "expected a blank line between the workflows and the ratings",
+        ));
     };
 
-    let mut rule_start = false;
-    let mut rule = Vec::new();
-    for c in line.chars() {
-        match c {
-            '}' => checker.otherwise = rule.iter().collect::<String>(),
-            '{' => rule_start = true,
-            ',' => {
-                checker.rules.push(parse_rule(&rule));
-                rule.clear();
-            }
-            _ => {
-                if rule_start {
-                    rule.push(c);
-                } else {
-                    name.push(c);
-                }
-            }
-        }
+    let workflows = lines[..blank]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_checker(i + 1, line))
+        .collect::<Result<Vec<(String, Checker)>, AocError>>()?;
+    let names = workflows
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<HashSet<&str>>();
+    if !names.contains("in") {
+        return Err(AocError::parse(blank, "no workflow named in"));
     }
 
-    (name.iter().collect::<String>(), checker)
-}
-
-fn parse_lines(lines: &Vec<String>) -> (HashMap<String, Checker>, Vec<Value>) {
-    let start = Instant::now();
-    let mut checkers = HashMap::new();
-    let mut i = 0;
-    while !lines[i].is_empty() {
-        let (name, checker) = parse_checker(&lines[i]);
-        checkers.insert(name, checker);
-        i += 1;
+    // Every rule has to send parts somewhere.
+    for (i, (_, checker)) in workflows.iter().enumerate() {
+        let dsts = checker.rules.iter().map(|rule| &rule.dst);
+        if let Some(dst) = dsts
+            .chain([&checker.otherwise])
+            .find(|&dst| dst != "A" && dst != "R" && !names.contains(dst.as_str()))
+        {
+            return Err(AocError::parse(
+                i + 1,
+                format!("no workflow named {:?}", dst),
+            ));
+        }
     }
+    let checkers = workflows.into_iter().collect::<HashMap<String, Checker>>();
 
-    let mut values = Vec::new();
-    while i < lines.len() {
-        values.push(parse_value(&lines[i]));
-        i += 1;
-    }
+    let values = lines
+        .iter()
+        .enumerate()
+        .skip(blank + 1)
+        .map(|(i, line)| parse_value(i + 1, line))
+        .collect::<Result<Vec<Value>, AocError>>()?;
 
-    println!("Parse Lines: elapsed: {:.2?}", start.elapsed());
-    (checkers, values)
+    Ok((checkers, values))
 }
 
-fn parse_value(line: &str) -> Value {
-    let value_re = Regex::new(r"\{x=(\d+),m=(\d+),a=(\d+),s=(\d+)\}").unwrap();
-    let mut value = Value {
-        x: 0,
-        m: 0,
-        a: 0,
-        s: 0,
-    };
-    for m in value_re.captures_iter(line) {
-        for (i, capt) in m.iter().enumerate() {
-            if let Some(sub) = capt {
-                match i {
-                    1 => value.x = sub.as_str().parse::<i64>().unwrap(),
-                    2 => value.m = sub.as_str().parse::<i64>().unwrap(),
-                    3 => value.a = sub.as_str().parse::<i64>().unwrap(),
-                    4 => value.s = sub.as_str().parse::<i64>().unwrap(),
-                    _ => {}
-                }
-            }
-        }
-    }
-
-    value
+/// A part's ratings, like `{x=787,m=2655,a=1222,s=2876}`.
+fn parse_value(line_no: usize, line: &str) -> Result<Value, AocError> {
+    let (x, m, a, s) = scan!(line_no, line, "{x={},m={},a={},s={}}" => i64, i64, i64, i64)?;
+    Ok(Value { x, m, a, s })
 }
 
 fn handle_pt1(checkers: &HashMap<String, Checker>, values: &Vec<Value>) -> i64 {
@@ -373,11 +320,15 @@ fn find_accepted_rule_paths(
     let check = checker.get(name).unwrap();
     let mut curr = value_range;
     for rule in &check.rules {
-        let mut pass = curr.clone();
-        pass.update(rule, true);
-        res.append(&mut find_accepted_rule_paths(checker, &rule.dst, pass));
+        let (pass, fail) = curr.split(rule);
+        if let Some(pass) = pass {
+            res.append(&mut find_accepted_rule_paths(checker, &rule.dst, pass));
+        }
 
-        curr.update(&rule, false);
+        match fail {
+            Some(fail) => curr = fail,
+            None => return res,
+        }
     }
 
     res.append(&mut find_accepted_rule_paths(
@@ -426,7 +377,7 @@ mod tests {
         )];
 
         for (input, (want1, want2)) in tests {
-            let (checker, values) = parse_lines(&input);
+            let (checker, values) = parse_lines(&input).unwrap();
             assert_eq!(
                 handle_pt1(&checker, &values),
                 want1,
@@ -441,4 +392,30 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = [
+            (vec!["in{s<1351:A,R}", "{x=787,m=2655,a=1222,s=2876}"], 2),
+            (vec!["in{s<1351:A,R}", "", "{x=787,m=2655,a=1222}"], 3),
+            (
+                vec!["in{s<1351:A,R}", "", "{x=787,m=26x5,a=1222,s=2876}"],
+                3,
+            ),
+            (vec!["in{s<1351:A,R}", "px{q<1:A,R}", ""], 2),
+            (vec!["in{s<1351:A,R}", "px{a=1:A,R}", ""], 2),
+            (vec!["in{s<1351:A,R}", "px{a<x:A,R}", ""], 2),
+            (vec!["in{s<1351:A,R}", "px{a<1,R}", ""], 2),
+            (vec!["in{s<1351:A,R}", "px a<1:A,R", ""], 2),
+            (vec!["in{s<1351:px,R}", "qs{a<1:A,R}", ""], 1),
+            (vec!["px{a<1:A,R}", ""], 1),
+        ];
+        for (lines, want) in tests {
+            let lines = lines.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            match parse_lines(&lines) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", lines),
+                _ => panic!("{:?} should fail to parse", lines),
+            }
+        }
+    }
 }
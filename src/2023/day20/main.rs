@@ -1,32 +1,44 @@
+#![allow(
+    clippy::needless_range_loop,
+    clippy::ptr_arg,
+    clippy::redundant_field_names
+)]
+
 use core::fmt;
 use std::{
     borrow::Borrow,
     collections::{HashMap, VecDeque},
 };
 
-use advent_of_code::utils::input::read_lines;
-use clap::Parser;
-use std::time::Instant;
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::cycle;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 20;
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(lines.to_vec())
+    }
 
-    let mut start = Instant::now();
-    println!("Part 1: {}", handle_pt1(&mut parse_lines(&lines)));
-    println!("Elapsed: {:.2?}", start.elapsed());
+    fn part1(lines: &Self::Input) -> i64 {
+        handle_pt1(&mut parse_lines(lines))
+    }
 
-    start = Instant::now();
-    println!("Part 2: {}", handle_pt2(&mut parse_lines(&lines)));
-    println!("Elapsed: {:.2?}", start.elapsed());
+    fn part2(lines: &Self::Input) -> i64 {
+        handle_pt2(&mut parse_lines(lines))
+    }
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
@@ -50,7 +62,7 @@ impl fmt::Display for ModuleType {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, PartialOrd, Ord)]
 enum Pulse {
     LO = 0,
     HI = 1,
@@ -70,7 +82,6 @@ impl fmt::Display for Pulse {
 }
 
 pub struct Module {
-    name: String,
     module_type: ModuleType,
     dsts: Vec<String>,
     on: bool,
@@ -122,20 +133,6 @@ impl Module {
 
         vec![].into()
     }
-
-    fn original(&self) -> bool {
-        match self.module_type {
-            // ModuleType::Conjunction => self.memory.iter().all(|(_, p)| *p == Pulse::LO),
-            ModuleType::FlipFlop => {
-                if self.on {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
-            _ => true,
-        }
-    }
 }
 
 impl fmt::Display for Module {
@@ -149,7 +146,7 @@ fn parse_line(line: &str) -> (String, Module) {
     let mut find_name = true;
     let mut chars = line.chars();
 
-    match chars.nth(0).unwrap() {
+    match chars.next().unwrap() {
         '%' => module_type = ModuleType::FlipFlop,
         '&' => module_type = ModuleType::Conjunction,
         _ => {
@@ -161,7 +158,7 @@ fn parse_line(line: &str) -> (String, Module) {
     let mut name = "broadcaster".to_string();
     let mut name_builder: Vec<char> = Vec::new();
     if find_name {
-        while let Some(c) = chars.nth(0) {
+        for c in chars.by_ref() {
             if c.is_whitespace() {
                 break;
             }
@@ -175,12 +172,10 @@ fn parse_line(line: &str) -> (String, Module) {
     (
         name.clone(),
         Module {
-            name: name.clone(),
             module_type: module_type,
             dsts: chars
                 .collect::<String>()
                 .split(", ")
-                .into_iter()
                 .map(|x| x.to_string())
                 .collect::<Vec<String>>(),
             memory: HashMap::new(),
@@ -190,7 +185,6 @@ fn parse_line(line: &str) -> (String, Module) {
 }
 
 fn parse_lines(lines: &Vec<String>) -> HashMap<String, Module> {
-    let start = Instant::now();
     let mut modules = HashMap::new();
     for i in 0..lines.len() {
         let (name, module) = parse_line(&lines[i]);
@@ -210,50 +204,61 @@ fn parse_lines(lines: &Vec<String>) -> HashMap<String, Module> {
         }
     }
 
-    println!("Parse Lines: elapsed: {:.2?}", start.elapsed());
     modules
 }
 
+// Everything a press can change, in a fixed order so it can be compared.
+fn snapshot(modules: &HashMap<String, Module>) -> Vec<(bool, Vec<Pulse>)> {
+    let mut names = modules.keys().collect::<Vec<&String>>();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let module = &modules[name];
+            let mut memory = module.memory.iter().collect::<Vec<(&String, &Pulse)>>();
+            memory.sort();
+            (module.on, memory.into_iter().map(|(_, p)| *p).collect())
+        })
+        .collect()
+}
+
+// Sends a low pulse to the broadcaster, counting every pulse that goes out.
+fn press(modules: &mut HashMap<String, Module>, button: usize, counts: &mut HashMap<Pulse, i64>) {
+    let mut queue: VecDeque<(String, Pulse, String)> = VecDeque::new();
+    queue.push_back((
+        format!("button {}", button).to_string(),
+        Pulse::LO,
+        "broadcaster".to_string(),
+    ));
+    while let Some((from, pulse, curr)) = queue.pop_front() {
+        counts.entry(pulse).and_modify(|e| *e += 1);
+        modules.entry(curr.clone()).and_modify(|e| {
+            for (send, dst) in e.process(&from, pulse) {
+                queue.push_back((curr.clone(), send, dst.clone()));
+            }
+        });
+    }
+}
+
 fn handle_pt1(modules: &mut HashMap<String, Module>) -> i64 {
     let mut counts = HashMap::new();
     counts.insert(Pulse::LO, 0);
     counts.insert(Pulse::HI, 0);
 
-    let mut button = 1;
-
     let total = 1000;
-    while button <= total {
-        let mut queue: VecDeque<(String, Pulse, String)> = VecDeque::new();
-        queue.push_back((
-            format!("button {}", button).to_string(),
-            Pulse::LO,
-            "broadcaster".to_string(),
-        ));
-        while let Some((from, pulse, curr)) = queue.pop_front() {
-            counts.entry(pulse).and_modify(|e| *e += 1);
-            modules.entry(curr.clone()).and_modify(|e| {
-                for (send, dst) in e.process(&from, pulse) {
-                    queue.push_back((curr.clone(), send, dst.clone()));
-                }
-            });
-        }
-
-        if modules.iter().all(|(_, m)| m.original()) {
-            break;
-        }
-
-        button += 1;
+    let mut lo = vec![0];
+    let mut hi = vec![0];
+    let presses = cycle::find_within(snapshot(modules), total, |_| {
+        press(modules, lo.len(), &mut counts);
+        lo.push(counts[&Pulse::LO]);
+        hi.push(counts[&Pulse::HI]);
+        snapshot(modules)
+    });
+
+    match presses {
+        Some(presses) => presses.extrapolate(&hi, total) * presses.extrapolate(&lo, total),
+        None => hi[total] * lo[total],
     }
-
-    let mut lo = *counts.get(&Pulse::LO).unwrap();
-    let mut hi = *counts.get(&Pulse::HI).unwrap();
-    let multiplier = total / button;
-    if button <= total {
-        lo *= multiplier;
-        hi *= multiplier;
-    }
-
-    hi * lo
 }
 
 fn handle_pt2(modules: &mut HashMap<String, Module>) -> i64 {
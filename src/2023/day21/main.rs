@@ -1,48 +1,65 @@
-use advent_of_code::utils::input::read_lines;
+#![allow(clippy::needless_return, clippy::ptr_arg, clippy::upper_case_acronyms)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+use advent_of_code::utils::math;
 use advent_of_code::utils::point::Point;
-use clap::Parser;
-use std::collections::{HashMap, HashSet, VecDeque};
+use advent_of_code::utils::viz::{self, Cell, Frame, Rgb, Visualizer};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::time::Instant;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input file.
-    #[arg(short, long)]
-    input: String,
+fn main() {
+    advent_of_code::solution::run::<Day>();
 }
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(args.input);
-    let (grid, dims, start) = create_grid(&lines);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 21;
+
+    type Input = (HashMap<Point<i64>, Plot>, Point<i64>, Point<i64>);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {
+        Ok(create_grid(&lines.to_vec()))
+    }
+
+    fn part1((grid, _, start): &Self::Input) -> i64 {
+        handle_pt1(grid, *start, 64)
+    }
 
-    let mut trace = Instant::now();
-    println!("Part 1: {}", handle_pt1(&grid, dims, start, 64));
-    println!("Elapsed: {:.2?}", trace.elapsed());
+    fn part2((grid, dims, start): &Self::Input) -> i64 {
+        handle_pt2(grid, *dims, *start, 26501365)
+    }
 
-    trace = Instant::now();
-    println!("Part 2: {}", handle_pt2(&grid, dims, start, 26501365));
-    println!("Elapsed: {:.2?}", trace.elapsed());
+    fn visualize((grid, dims, start): &Self::Input, viz: &mut Visualizer) {
+        let mut possible = HashSet::from([*start]);
+        viz.frame(&draw_plots(grid, *dims, &possible));
+        for _ in 0..64 {
+            possible = step(grid, &possible);
+            viz.frame(&draw_plots(grid, *dims, &possible));
+        }
+    }
 }
 
-enum Plot {
+pub enum Plot {
     GARDEN = 1,
     ROCK = 2,
 }
 
-impl fmt::Display for Plot{
-   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-       write!(
-           f,
-           "{}",
-           match self {
-               Plot::GARDEN=> ".",
-               Plot::ROCK => "#",
-           }
-       )
-   }
+impl fmt::Display for Plot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Plot::GARDEN => ".",
+                Plot::ROCK => "#",
+            }
+        )
+    }
 }
 fn create_grid(lines: &Vec<String>) -> (HashMap<Point<i64>, Plot>, Point<i64>, Point<i64>) {
     let height = lines.len() as i64;
@@ -79,89 +96,6 @@ fn create_grid(lines: &Vec<String>) -> (HashMap<Point<i64>, Plot>, Point<i64>, P
     )
 }
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
-enum Direction {
-    WEST = 1,
-    EAST = 2,
-    NORTH = 3,
-    SOUTH = 4,
-}
-
-#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
-enum Dist {
-    ODD = 1,
-    EVEN = 2,
-    BOTH = 3,
-}
-
-impl fmt::Display for Dist {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Dist::EVEN => "even",
-                Dist::ODD => "odd",
-                Dist::BOTH => "both",
-            }
-        )
-    }
-}
-impl fmt::Display for Direction {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Direction::SOUTH => "S",
-                Direction::EAST => "E",
-                Direction::NORTH => "N",
-                Direction::WEST => "W",
-            }
-        )
-    }
-}
-
-fn go(p: Point<i64>, dir: Direction, dims: Point<i64>) -> Option<Point<i64>> {
-    let new_point = Point {
-        x: p.x
-            + match dir {
-                Direction::EAST => 1,
-                Direction::WEST => -1,
-                _ => 0,
-            },
-        y: p.y
-            + match dir {
-                Direction::NORTH => -1,
-                Direction::SOUTH => 1,
-                _ => 0,
-            },
-    };
-
-    if new_point.x < 0 || new_point.x >= dims.x || new_point.y < 0 || new_point.y >= dims.y {
-        return None;
-    }
-
-    Some(new_point)
-}
-
-fn go_void(p: Point<i64>, dir: Direction) -> Point<i64> {
-    Point {
-        x: p.x
-            + match dir {
-                Direction::EAST => 1,
-                Direction::WEST => -1,
-                _ => 0,
-            },
-        y: p.y
-            + match dir {
-                Direction::NORTH => -1,
-                Direction::SOUTH => 1,
-                _ => 0,
-            },
-    }
-}
-
 fn real_coord(p: Point<i64>, dims: Point<i64>) -> Point<i64> {
     Point {
         x: p.x.rem_euclid(dims.x),
@@ -169,176 +103,131 @@ fn real_coord(p: Point<i64>, dims: Point<i64>) -> Point<i64> {
     }
 }
 
-fn handle_pt1(
-    grid: &HashMap<Point<i64>, Plot>,
-    dims: Point<i64>,
-    start: Point<i64>,
-    total_steps: i64,
-) -> i64 {
-    let mut steps = 0;
-
-    let mut possible = HashSet::new();
-    possible.insert(start);
+fn handle_pt1(grid: &HashMap<Point<i64>, Plot>, start: Point<i64>, total_steps: i64) -> i64 {
+    let mut possible = HashSet::from([start]);
+    for _ in 0..total_steps {
+        possible = step(grid, &possible);
+    }
 
-    while steps < total_steps {
-        steps += 1;
+    possible.len() as i64
+}
 
-        let mut next_possible = HashSet::new();
-        for p in possible.iter() {
-            for dir in vec![
-                Direction::NORTH,
-                Direction::EAST,
-                Direction::WEST,
-                Direction::SOUTH,
-            ] {
-                if let Some(new_p) = go(*p, dir, dims) {
-                    if let Some(plot) = grid.get(&new_p) {
-                        match plot {
-                            Plot::ROCK => continue,
-                            Plot::GARDEN => {
-                                next_possible.insert(new_p);
-                            }
-                        }
-                    }
-                }
+/// Every garden plot one step away from somewhere in `possible`.
+fn step(grid: &HashMap<Point<i64>, Plot>, possible: &HashSet<Point<i64>>) -> HashSet<Point<i64>> {
+    let mut next_possible = HashSet::new();
+    for p in possible.iter() {
+        for new_p in p.neighbors4() {
+            if let Some(Plot::GARDEN) = grid.get(&new_p) {
+                next_possible.insert(new_p);
             }
         }
-
-        possible = next_possible;
     }
-
-    possible.len() as i64
+    next_possible
 }
 
-fn is_odd(x: i64) -> bool {
-    return x.rem_euclid(2) == 1;
+fn draw_plots(
+    grid: &HashMap<Point<i64>, Plot>,
+    dims: Point<i64>,
+    possible: &HashSet<Point<i64>>,
+) -> Frame {
+    viz::draw(Point::new(0, 0), dims - Point::new(1, 1), |p| {
+        match grid.get(&p) {
+            Some(Plot::ROCK) => Cell::colored('#', Rgb(130, 130, 140)),
+            _ if possible.contains(&p) => Cell::colored('O', Rgb(90, 200, 90)),
+            _ => Cell::from('.'),
+        }
+    })
 }
 
+/// How many plots can be reached in exactly `total_steps` steps on the map
+/// tiled out forever.
+///
+/// Walking back and forth means a plot can be reached in exactly `n` steps
+/// when it's at most `n` away and an even number of steps short of it, so
+/// this counts the plots found at each distance by a BFS. Once the BFS is
+/// out past the first few copies of the map, every period it picks up the
+/// same plots a copy further out, so the counts a period apart grow
+/// quadratically and can be carried out to `total_steps` from a few samples.
 fn handle_pt2(
     grid: &HashMap<Point<i64>, Plot>,
     dims: Point<i64>,
     start: Point<i64>,
     total_steps: i64,
 ) -> i64 {
-   //  println!("start = {}, dims = {}", start, dims);
-
-    let mut visited: HashSet<(Point<i64>, Dist)> = HashSet::new();
-
-    let mut next = VecDeque::new();
-    next.push_back((start, 0));
-
-    while let Some((p, steps)) = next.pop_front() {
-        if visited.contains(&(p, Dist::BOTH)) {
-
-            continue;
-        } else if (!is_odd(steps) && visited.contains(&(p, Dist::EVEN)))
-            || (is_odd(steps) && visited.contains(&(p, Dist::ODD)))
-        {
-            continue;
-        } else if (is_odd(steps) && visited.contains(&(p, Dist::EVEN)))
-            || (!is_odd(steps) && visited.contains(&(p, Dist::ODD)))
-        {
-            visited.insert((p, Dist::BOTH));
-        } else {
-            visited.insert((
-                p,
-                match is_odd(steps) {
-                    true => Dist::ODD,
-                    false => Dist::EVEN,
-                },
-            ));
-        }
-
-        //   println!("\n\nSTEP {}", steps);
-
+    let period = math::lcm(dims.x, dims.y);
+    let is_garden = |p: &Point<i64>| matches!(grid.get(&real_coord(*p, dims)), Some(Plot::GARDEN));
+
+    // Each step is next to one from the step before or after, never the same
+    // one, so the previous layer is all that's needed to spot plots already
+    // found.
+    let mut prev = HashSet::new();
+    let mut layer = HashSet::from([start]);
+    // How many plots have been found an even and an odd distance away.
+    let mut found = [1, 0];
+    // The counts for steps that are a whole number of periods short of
+    // `total_steps`.
+    let mut samples: Vec<i64> = Vec::new();
+    let mut steps = 0;
+    loop {
         if steps == total_steps {
-            // println!("p {} is at total steps {}", p, steps);
-            continue;
+            return found[(steps % 2) as usize];
         }
-        for dir in vec![
-            Direction::NORTH,
-            Direction::EAST,
-            Direction::WEST,
-            Direction::SOUTH,
-        ] {
-            let new_p = go_void(p, dir);
-            let real = real_coord(new_p, dims);
-            let plot = grid.get(&real).unwrap();
-            match plot {
-                Plot::ROCK => continue,
-                Plot::GARDEN => {
-                    next.push_back((new_p, steps + 1));
-                }
+        if (total_steps - steps) % period == 0 {
+            samples.push(found[(steps % 2) as usize]);
+            if let Some(count) = extrapolate(&samples, (total_steps - steps) / period) {
+                return count;
             }
-            //  println!("{} -{}-> {} (really {})", p, dir, new_p, real);
         }
-    }
 
-    let mut possible = HashSet::new();
-    for (p, v) in visited {
-      //   println!("possible {} {}", p, v);
-        if v == Dist::BOTH
-            || v == match is_odd(total_steps) {
-                true => Dist::ODD,
-                false => Dist::EVEN,
-            }
-        {
-            possible.insert(p);
-            // println!("possible {}", p);
-        }
+        let next = layer
+            .iter()
+            .flat_map(|p| p.neighbors4())
+            .filter(|p| is_garden(p) && !prev.contains(p))
+            .collect::<HashSet<Point<i64>>>();
+        steps += 1;
+        found[(steps % 2) as usize] += next.len() as i64;
+        prev = std::mem::replace(&mut layer, next);
     }
-   //  println!("{}" , possible.iter().map(|x|x.to_string()).collect::<Vec<String>>().join("\n"));
+}
 
-   //  println!("");
-   //  for y in 0..dims.y {
-   //    for x in 0..dims.x {
-   //       let p = Point{x,y};
-   //       print!("{}", match grid.get(&p) {
-   //          Some(Plot::ROCK) => "#",
-   //          Some(Plot::GARDEN) => match possible.contains(&p) {
-   //             true => "O",
-   //             false => ".",
-   //          },
-   //          _ => unreachable!(),
-   //       })
-   //    }
-   //       // println!("");
-   //  }
+/// The value `k` samples after the last of `samples`, if the last few have
+/// settled into a quadratic (their second differences match).
+fn extrapolate(samples: &[i64], k: i64) -> Option<i64> {
+    // Enough matching second differences to trust they've settled.
+    const SETTLED: usize = 3;
+    if samples.len() < SETTLED + 2 {
+        return None;
+    }
+    let diffs = samples
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .collect::<Vec<i64>>();
+    let second = diffs.windows(2).map(|w| w[1] - w[0]).collect::<Vec<i64>>();
+    let recent = &second[second.len() - SETTLED..];
+    if recent.iter().any(|&d| d != recent[0]) {
+        return None;
+    }
 
-    possible.len() as i64
+    let (last, diff, second) = (*samples.last().unwrap(), *diffs.last().unwrap(), recent[0]);
+    return Some(last + k * diff + k * (k + 1) / 2 * second);
 }
 
+advent_of_code::example_tests!(Day, "2023/day21");
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::examples;
 
     #[test]
     fn test_handle_pt1() {
-        let tests = [(
-            (
-                vec![
-                    "...........",
-                    ".....###.#.",
-                    ".###.##..#.",
-                    "..#.#...#..",
-                    "....#.#....",
-                    ".##..S####.",
-                    ".##..#...#.",
-                    ".......##..",
-                    ".##.#.####.",
-                    ".##..##.##.",
-                    "...........",
-                ],
-                6,
-            ),
-            16,
-        )];
+        let input = examples::lines("examples/2023/day21/1.txt");
+        let tests = [(6, 16)];
 
-        for ((input, steps), want) in tests {
-            let (grid, dims, start) =
-                create_grid(&input.iter().map(|x| x.to_string()).collect::<Vec<String>>());
+        for (steps, want) in tests {
+            let (grid, _, start) = create_grid(&input);
             assert_eq!(
-                handle_pt1(&grid, dims, start, steps),
+                handle_pt1(&grid, start, steps),
                 want,
                 "with input\n{}",
                 input.join("\n")
@@ -347,22 +236,7 @@ mod tests {
     }
     #[test]
     fn test_handle_pt2() {
-        let input = vec![
-            "...........",
-            ".....###.#.",
-            ".###.##..#.",
-            "..#.#...#..",
-            "....#.#....",
-            ".##..S####.",
-            ".##..#...#.",
-            ".......##..",
-            ".##.#.####.",
-            ".##..##.##.",
-            "...........",
-        ]
-        .iter()
-        .map(|x| x.to_string())
-        .collect::<Vec<String>>();
+        let input = examples::lines("examples/2023/day21/1.txt");
         let tests = [
             (6, 16),
             (10, 50),
@@ -0,0 +1,94 @@
+// Each day is also built as its own binary, which is the only place its
+// `main` is used.
+#![allow(dead_code)]
+
+use crate::registry::Entry;
+
+#[path = "day01/main.rs"]
+pub mod day01;
+
+#[path = "day02/main.rs"]
+pub mod day02;
+
+#[path = "day03/main.rs"]
+pub mod day03;
+
+#[path = "day04/main.rs"]
+pub mod day04;
+
+#[path = "day05/main.rs"]
+pub mod day05;
+
+#[path = "day06/main.rs"]
+pub mod day06;
+
+#[path = "day07/main.rs"]
+pub mod day07;
+
+#[path = "day08/main.rs"]
+pub mod day08;
+
+#[path = "day09/main.rs"]
+pub mod day09;
+
+#[path = "day10/main.rs"]
+pub mod day10;
+
+#[path = "day11/main.rs"]
+pub mod day11;
+
+#[path = "day12/main.rs"]
+pub mod day12;
+
+#[path = "day13/main.rs"]
+pub mod day13;
+
+#[path = "day14/main.rs"]
+pub mod day14;
+
+#[path = "day15/main.rs"]
+pub mod day15;
+
+#[path = "day16/main.rs"]
+pub mod day16;
+
+#[path = "day17/main.rs"]
+pub mod day17;
+
+#[path = "day18/main.rs"]
+pub mod day18;
+
+#[path = "day19/main.rs"]
+pub mod day19;
+
+#[path = "day20/main.rs"]
+pub mod day20;
+
+#[path = "day21/main.rs"]
+pub mod day21;
+
+pub fn days() -> Vec<Entry> {
+    vec![
+        Entry::of::<day01::Day>(),
+        Entry::of::<day02::Day>(),
+        Entry::of::<day03::Day>(),
+        Entry::of::<day04::Day>(),
+        Entry::of::<day05::Day>(),
+        Entry::of::<day06::Day>(),
+        Entry::of::<day07::Day>(),
+        Entry::of::<day08::Day>(),
+        Entry::of::<day09::Day>(),
+        Entry::of::<day10::Day>(),
+        Entry::of::<day11::Day>(),
+        Entry::of::<day12::Day>(),
+        Entry::of::<day13::Day>(),
+        Entry::of::<day14::Day>(),
+        Entry::of::<day15::Day>(),
+        Entry::of::<day16::Day>(),
+        Entry::of::<day17::Day>(),
+        Entry::of::<day18::Day>(),
+        Entry::of::<day19::Day>(),
+        Entry::of::<day20::Day>(),
+        Entry::of::<day21::Day>(),
+    ]
+}
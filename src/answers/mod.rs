@@ -0,0 +1,256 @@
+use crate::error::AocError;
+use crate::solution::Unsolved;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// What's been submitted for one year, read from `answers/<year>.toml`:
+///
+/// ```toml
+/// [day16.part1]
+/// answer = 1651
+/// too_low = [1180, 1250]
+/// ```
+///
+/// `wrong` holds rejected guesses the site didn't give a direction for.
+#[derive(Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct Answers {
+    days: HashMap<String, Day>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Day {
+    part1: Option<Part>,
+    part2: Option<Part>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Part {
+    answer: Option<Answer>,
+    #[serde(default)]
+    too_low: Vec<Answer>,
+    #[serde(default)]
+    too_high: Vec<Answer>,
+    #[serde(default)]
+    wrong: Vec<Answer>,
+}
+
+/// Most answers are numbers, but some are text, so accept either.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Direction {
+    TooLow,
+    TooHigh,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Verdict {
+    /// Matches the accepted answer.
+    Pass,
+    /// Differs from the accepted answer.
+    Fail { expected: String },
+    /// Nothing's been accepted for this part yet.
+    New,
+    /// Matches a guess that was already rejected.
+    KnownWrong(Option<Direction>),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::New => write!(f, "NEW"),
+            Verdict::KnownWrong(Some(Direction::TooLow)) => write!(f, "WRONG (too low)"),
+            Verdict::KnownWrong(Some(Direction::TooHigh)) => write!(f, "WRONG (too high)"),
+            Verdict::KnownWrong(None) => write!(f, "WRONG"),
+        }
+    }
+}
+
+impl Answers {
+    /// Fails if the TOML doesn't match the layout above.
+    pub fn parse(contents: &str) -> Result<Self, AocError> {
+        toml::from_str(contents).map_err(|e| {
+            let line = match e.span() {
+                Some(span) => contents[..span.start].matches('\n').count() + 1,
+                None => 1,
+            };
+            AocError::parse(line, e.message())
+        })
+    }
+
+    /// A year without an answers file has nothing recorded.
+    pub fn load(year: u16) -> Result<Self, AocError> {
+        let path = path(year);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(AocError::Io {
+                path: path.display().to_string(),
+                source,
+            }),
+        }
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        let Some(recorded) = self.part(day, part) else {
+            return Verdict::New;
+        };
+
+        let matches = |guesses: &Vec<Answer>| guesses.iter().any(|g| g.to_string() == answer);
+        if matches(&recorded.too_low) {
+            return Verdict::KnownWrong(Some(Direction::TooLow));
+        }
+        if matches(&recorded.too_high) {
+            return Verdict::KnownWrong(Some(Direction::TooHigh));
+        }
+        if matches(&recorded.wrong) {
+            return Verdict::KnownWrong(None);
+        }
+
+        return match &recorded.answer {
+            Some(expected) if expected.to_string() == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::New,
+        };
+    }
+
+    fn part(&self, day: u8, part: u8) -> Option<&Part> {
+        let day = self.days.get(&format!("day{:02}", day))?;
+        return match part {
+            1 => day.part1.as_ref(),
+            2 => day.part2.as_ref(),
+            _ => None,
+        };
+    }
+}
+
+fn path(year: u16) -> PathBuf {
+    PathBuf::from(format!("answers/{}.toml", year))
+}
+
+/// Checks a part's answer against `answers/<year>.toml` and prints the
+/// verdict. Meant to be called right after the answer itself is printed.
+pub fn report(year: u16, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+    if answer == Unsolved.to_string() {
+        return None;
+    }
+
+    // A typo in the file shouldn't stop the day from running.
+    let verdict = match Answers::load(year) {
+        Ok(answers) => answers.check(day, part, answer),
+        Err(e) => {
+            eprintln!("  warning: ignoring {}: {}", path(year).display(), e);
+            Verdict::New
+        }
+    };
+    match verdict {
+        Verdict::KnownWrong(_) => eprintln!(
+            "  warning: {} was already submitted for part {} and marked {}",
+            answer, part, verdict
+        ),
+        _ => println!("  {}", verdict),
+    }
+    return Some(verdict);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(
+            r#"
+            [day16.part1]
+            answer = 1651
+            too_low = [1180, 1250]
+            too_high = [1800]
+
+            [day16.part2]
+            wrong = [1200]
+
+            [day10.part2]
+            answer = "EHZFZHCZ"
+            "#,
+        )
+        .unwrap();
+
+        let tests = [
+            ((16, 1, "1651"), Verdict::Pass),
+            (
+                (16, 1, "1600"),
+                Verdict::Fail {
+                    expected: String::from("1651"),
+                },
+            ),
+            (
+                (16, 1, "1250"),
+                Verdict::KnownWrong(Some(Direction::TooLow)),
+            ),
+            (
+                (16, 1, "1800"),
+                Verdict::KnownWrong(Some(Direction::TooHigh)),
+            ),
+            ((16, 2, "1200"), Verdict::KnownWrong(None)),
+            ((16, 2, "1707"), Verdict::New),
+            ((10, 2, "EHZFZHCZ"), Verdict::Pass),
+            ((10, 1, "13140"), Verdict::New),
+            ((3, 1, "7"), Verdict::New),
+        ];
+
+        for ((day, part, answer), want) in tests {
+            assert_eq!(answers.check(day, part, answer), want);
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let tests = [
+            // Not TOML at all.
+            ("[day16.part1]\nanswer = ", 2),
+            // TOML, but the answer is the wrong type.
+            ("[day16.part1]\n\n[day16.part2]\nanswer = 1.5\n", 4),
+        ];
+        for (contents, want) in tests {
+            match Answers::parse(contents) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, want, "{:?}", contents),
+                other => panic!("{:?} should fail to parse, got {:?}", contents, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let answers = Answers::load(2022).unwrap();
+        assert_eq!(answers.check(19, 2, "13340"), Verdict::Pass);
+        assert_eq!(
+            answers.check(16, 1, "1180"),
+            Verdict::KnownWrong(Some(Direction::TooLow))
+        );
+        let answers = Answers::load(1999).unwrap();
+        assert_eq!(answers.check(1, 1, "1"), Verdict::New);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::AocError;
+use crate::registry::Entry;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// How long one phase (parsing or a part) took across every iteration.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn of(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Parses `lines` and runs both parts `iterations` times, timing each phase.
+pub fn bench(entry: &Entry, lines: &[String], iterations: usize) -> Result<Report, AocError> {
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let input = entry.parse(lines)?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        entry.part1(input.as_ref());
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        entry.part2(input.as_ref());
+        part2.push(start.elapsed());
+    }
+
+    return Ok(Report {
+        year: entry.year,
+        day: entry.day,
+        iterations: parse.len(),
+        parse: Stats::of(parse),
+        part1: Stats::of(part1),
+        part2: Stats::of(part2),
+    });
+}
+
+/// One row per day, with min / median / max for each phase.
+pub fn table(reports: &[Report]) -> String {
+    let mut rows = vec![[
+        String::from("day"),
+        String::from("parse (min / median / max)"),
+        String::from("part 1 (min / median / max)"),
+        String::from("part 2 (min / median / max)"),
+        String::from("total (median)"),
+    ]];
+    for r in reports {
+        rows.push([
+            format!("{} day {:02}", r.year, r.day),
+            fmt_stats(&r.parse),
+            fmt_stats(&r.part1),
+            fmt_stats(&r.part2),
+            format!("{:.2?}", r.parse.median + r.part1.median + r.part2.median),
+        ]);
+    }
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let cells = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{:<width$}", cell, width = widths[i]))
+            .collect::<Vec<String>>();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    return out;
+}
+
+fn fmt_stats(stats: &Stats) -> String {
+    format!(
+        "{:.2?} / {:.2?} / {:.2?}",
+        stats.min, stats.median, stats.max
+    )
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    year: u16,
+    day: u8,
+    iterations: usize,
+    parse: JsonStats,
+    part1: JsonStats,
+    part2: JsonStats,
+}
+
+/// Durations in nanoseconds.
+#[derive(Serialize)]
+struct JsonStats {
+    min: u128,
+    median: u128,
+    max: u128,
+}
+
+impl From<&Stats> for JsonStats {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            min: stats.min.as_nanos(),
+            median: stats.median.as_nanos(),
+            max: stats.max.as_nanos(),
+        }
+    }
+}
+
+/// The same as `table`, as a JSON array with durations in nanoseconds.
+pub fn json(reports: &[Report]) -> String {
+    let reports = reports
+        .iter()
+        .map(|r| JsonReport {
+            year: r.year,
+            day: r.day,
+            iterations: r.iterations,
+            parse: (&r.parse).into(),
+            part1: (&r.part1).into(),
+            part2: (&r.part2).into(),
+        })
+        .collect::<Vec<JsonReport>>();
+    return serde_json::to_string_pretty(&reports).expect("reports always serialize");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let tests = [
+            (vec![ms(5)], (ms(5), ms(5), ms(5))),
+            (vec![ms(3), ms(1), ms(2)], (ms(1), ms(2), ms(3))),
+            (vec![ms(9), ms(1), ms(4), ms(2)], (ms(1), ms(4), ms(9))),
+        ];
+
+        for (samples, (min, median, max)) in tests {
+            assert_eq!(Stats::of(samples), Stats { min, median, max });
+        }
+    }
+
+    #[test]
+    fn test_bench() {
+        let lines = vec![String::from("0 3 6 9 12 15")];
+        let report = bench(&registry::get(2023, 9).unwrap(), &lines, 3).unwrap();
+        assert_eq!((report.year, report.day, report.iterations), (2023, 9, 3));
+
+        let table = table(std::slice::from_ref(&report));
+        assert!(table.starts_with("day "));
+        assert!(table.lines().nth(1).unwrap().starts_with("2023 day 09  "));
+
+        let json: serde_json::Value = serde_json::from_str(&json(&[report])).unwrap();
+        assert_eq!(json[0]["day"], 9);
+        assert!(json[0]["part1"]["median"].is_u64());
+    }
+}
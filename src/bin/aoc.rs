@@ -0,0 +1,209 @@
+#![allow(clippy::needless_return)]
+
+use advent_of_code::answers::{self, Verdict};
+use advent_of_code::bench::{self, Report};
+use advent_of_code::registry::{self, Entry};
+use advent_of_code::utils::input::Source;
+use clap::{Parser, Subcommand};
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one day, every day of a year, or everything.
+    Run(RunArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    /// Year of the puzzle, e.g. 2023.
+    year: Option<u16>,
+
+    /// Day of the puzzle, e.g. 17.
+    day: Option<u8>,
+
+    /// Run every day of this year.
+    #[arg(long = "year", id = "whole_year", conflicts_with_all = ["year", "day", "all"])]
+    whole_year: Option<u16>,
+
+    /// Run every registered day.
+    #[arg(long, conflicts_with_all = ["year", "day"])]
+    all: bool,
+
+    /// Only run this part.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file, or `-` for stdin. Defaults to inputs/<year>/day<day>.txt.
+    #[arg(short, long, requires = "day", conflicts_with = "data")]
+    input: Option<String>,
+
+    /// The puzzle input itself, instead of a file.
+    #[arg(short, long, requires = "day")]
+    data: Option<String>,
+
+    /// Time parsing and each part over N runs instead of printing answers.
+    #[arg(long, value_name = "N")]
+    bench: Option<usize>,
+
+    /// Print the benchmark results as JSON.
+    #[arg(long, requires = "bench")]
+    json: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(args),
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    // Benchmark output may be piped somewhere, so keep notes out of it.
+    let note = |msg: String| match args.bench {
+        Some(_) => eprintln!("{}", msg),
+        None => println!("{}", msg),
+    };
+
+    // Unimplemented days are kept as `Err` so they're reported in order.
+    let mut entries = Vec::new();
+    if args.all {
+        entries = registry::all().into_iter().map(Ok).collect();
+    } else if let Some(year) = args.whole_year.or(args.year.filter(|_| args.day.is_none())) {
+        for day in 1..=25 {
+            entries.push(registry::get(year, day).ok_or((year, day)));
+        }
+    } else {
+        let (Some(year), Some(day)) = (args.year, args.day) else {
+            eprintln!(
+                "expected `aoc run <YEAR> <DAY>`, `aoc run --year <YEAR>` or `aoc run --all`"
+            );
+            return ExitCode::FAILURE;
+        };
+        let Some(entry) = registry::get(year, day) else {
+            eprintln!("{} day {:02}: not implemented", year, day);
+            return ExitCode::FAILURE;
+        };
+        entries.push(Ok(entry));
+    }
+
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err((year, day)) => {
+                note(format!("{} day {:02}: not implemented", year, day));
+                continue;
+            }
+        };
+        let source = Source::resolve(
+            entry.year,
+            entry.day,
+            args.input.as_deref(),
+            args.data.as_deref(),
+        );
+        let outcome = match args.bench {
+            Some(iterations) => bench_entry(&entry, &source, iterations, &mut reports),
+            None => run_entry(&entry, &source, args.part),
+        };
+        failed |= match outcome {
+            Outcome::Done => false,
+            // Missing inputs are expected when running more than one day.
+            Outcome::MissingInput => args.day.is_some(),
+            Outcome::Failed => true,
+        };
+    }
+
+    if args.json {
+        println!("{}", bench::json(&reports));
+    } else if args.bench.is_some() {
+        print!("{}", bench::table(&reports));
+    }
+
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+/// How running or benchmarking one day went.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Outcome {
+    Done,
+    /// There's no input for the day to run on.
+    MissingInput,
+    /// The input couldn't be read or parsed, or an answer didn't match the
+    /// one recorded for it.
+    Failed,
+}
+
+fn missing_input(source: &Source) -> Option<String> {
+    match source {
+        Source::File(path) if !path.exists() => Some(format!("no input at {}", path.display())),
+        _ => None,
+    }
+}
+
+fn run_entry(entry: &Entry, source: &Source, part: Option<u8>) -> Outcome {
+    println!("{} day {:02}", entry.year, entry.day);
+    if let Some(msg) = missing_input(source) {
+        println!("  {}", msg);
+        return Outcome::MissingInput;
+    }
+
+    let parsed = match source.read().and_then(|lines| entry.parse(&lines)) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            println!("  error: {}", err);
+            return Outcome::Failed;
+        }
+    };
+    let mut outcome = Outcome::Done;
+    let mut check = |part: u8, answer: String| {
+        println!("  Part {}: {}", part, answer);
+        match answers::report(entry.year, entry.day, part, &answer) {
+            Some(Verdict::Fail { .. }) | Some(Verdict::KnownWrong(_)) => outcome = Outcome::Failed,
+            _ => {}
+        }
+    };
+    if part.unwrap_or(1) == 1 {
+        check(1, entry.part1(parsed.as_ref()));
+    }
+    if part.unwrap_or(2) == 2 {
+        check(2, entry.part2(parsed.as_ref()));
+    }
+    for line in entry.diagnostics(parsed.as_ref()) {
+        println!("  {}", line);
+    }
+    return outcome;
+}
+
+fn bench_entry(
+    entry: &Entry,
+    source: &Source,
+    iterations: usize,
+    reports: &mut Vec<Report>,
+) -> Outcome {
+    if let Some(msg) = missing_input(source) {
+        eprintln!("{} day {:02}: {}", entry.year, entry.day, msg);
+        return Outcome::MissingInput;
+    }
+    let result = source
+        .read()
+        .and_then(|lines| bench::bench(entry, &lines, iterations));
+    match result {
+        Ok(report) => reports.push(report),
+        Err(err) => {
+            eprintln!("{} day {:02}: error: {}", entry.year, entry.day, err);
+            return Outcome::Failed;
+        }
+    }
+    return Outcome::Done;
+}
@@ -0,0 +1,420 @@
+#![allow(clippy::needless_return)]
+
+use advent_of_code::utils::input::cached_path;
+use clap::Parser;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+/// Scaffolds a new day: its `main.rs`, `[[bin]]` entry, registry wiring, an
+/// empty input and an example fixture.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Year of the puzzle, e.g. 2024.
+    #[arg(value_parser = clap::value_parser!(u16).range(2015..))]
+    year: u16,
+
+    /// Day of the puzzle, e.g. 3.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let files = match plan(root, args.year, args.day) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for (path, contents) in &files {
+        fs::create_dir_all(path.parent().unwrap()).expect("couldn't create directory");
+        fs::write(path, contents).expect("couldn't write file");
+        println!(
+            "wrote {}",
+            path.strip_prefix(root).unwrap_or(path).display()
+        );
+    }
+
+    // Best effort: the generated code is close to formatted already.
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .args(
+            files
+                .iter()
+                .map(|(p, _)| p)
+                .filter(|p| p.extension() == Some("rs".as_ref())),
+        )
+        .status();
+    return ExitCode::SUCCESS;
+}
+
+/// Every file to write, with its full contents. Nothing is written if the day
+/// already exists.
+fn plan(root: &Path, year: u16, day: u8) -> Result<Vec<(PathBuf, String)>, String> {
+    let dir = format!("src/{}/day{:02}", year, day);
+    let main = root.join(&dir).join("main.rs");
+    if main.exists() {
+        return Err(format!("{}/main.rs already exists", dir));
+    }
+
+    let mut files = vec![(main, template(year, day))];
+
+    let manifest = read(&root.join("Cargo.toml"))?;
+    files.push((root.join("Cargo.toml"), add_bin(&manifest, year, day)?));
+
+    let year_mod = root.join(format!("src/{}/mod.rs", year));
+    if year_mod.exists() {
+        let existing = read(&year_mod)?;
+        files.push((year_mod, year_module(&existing, day)));
+    } else {
+        files.push((year_mod, year_module("", day)));
+
+        let lib = root.join("src/lib.rs");
+        files.push((lib.clone(), add_year_to_lib(&read(&lib)?, year)?));
+
+        let registry = root.join("src/registry/mod.rs");
+        files.push((
+            registry.clone(),
+            add_year_to_registry(&read(&registry)?, year)?,
+        ));
+    }
+
+    let input = root.join(cached_path(year, day));
+    if !input.exists() {
+        files.push((input, String::new()));
+    }
+
+    let example = root.join(format!("examples/{}/day{:02}/1.txt", year, day));
+    if !example.exists() {
+        files.push((
+            example.with_extension("toml"),
+            String::from(EXAMPLE_ANSWERS),
+        ));
+        files.push((example, String::new()));
+    }
+
+    return Ok(files);
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+}
+
+const EXAMPLE_ANSWERS: &str = "# Expected answers for 1.txt.
+# part1 = 0
+# part2 = 0
+";
+
+fn template(year: u16, day: u8) -> String {
+    format!(
+        r#"#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use advent_of_code::error::AocError;
+use advent_of_code::solution::Solution;
+
+fn main() {{
+    advent_of_code::solution::run::<Day>();
+}}
+
+pub struct Day;
+
+impl Solution for Day {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError> {{
+        Ok(lines.to_vec())
+    }}
+
+    fn part1(lines: &Self::Input) -> i64 {{
+        handle_pt1(lines)
+    }}
+
+    fn part2(lines: &Self::Input) -> i64 {{
+        handle_pt2(lines)
+    }}
+}}
+
+fn handle_pt1(_lines: &Vec<String>) -> i64 {{
+    return 0;
+}}
+
+fn handle_pt2(_lines: &Vec<String>) -> i64 {{
+    return 0;
+}}
+
+advent_of_code::example_tests!(Day, "{year}/day{day:02}");
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_handle_pt1() {{
+        let tests = [(vec![String::from("")], 0)];
+
+        for (input, want) in tests {{
+            assert_eq!(handle_pt1(&input), want, "for input\n{{}}", input.join("\n"));
+        }}
+    }}
+}}
+"#
+    )
+}
+
+/// Inserts `block` next to the other matches of `re`, keeping them ordered
+/// by the first capture group, with `sep` between neighbouring blocks.
+fn insert_sorted(text: &str, re: &Regex, key: &str, block: &str, sep: &str) -> Option<String> {
+    let mut last = None;
+    for caps in re.captures_iter(text) {
+        let m = caps.get(0).unwrap();
+        if &caps[1] > key {
+            return Some(format!(
+                "{}{}{}{}",
+                &text[..m.start()],
+                block,
+                sep,
+                &text[m.start()..]
+            ));
+        }
+        last = Some(m.end());
+    }
+    let end = last?;
+    return Some(format!("{}{}{}{}", &text[..end], sep, block, &text[end..]));
+}
+
+fn add_bin(manifest: &str, year: u16, day: u8) -> Result<String, String> {
+    let name = format!("{}day{:02}", year, day);
+    if manifest.contains(&format!("name = \"{}\"", name)) {
+        return Err(format!("Cargo.toml already has a {} bin", name));
+    }
+
+    let block = format!(
+        "[[bin]]\nname = \"{}\"\npath = \"src/{}/day{:02}/main.rs\"\n",
+        name, year, day
+    );
+    let re = Regex::new(r#"(?m)^\[\[bin\]\]\nname = "(\d{4}day\d{2})"\npath = ".*"\n"#).unwrap();
+    return match insert_sorted(manifest, &re, &name, &block, "\n") {
+        Some(manifest) => Ok(manifest),
+        None => Ok(format!("{}\n{}", manifest, block)),
+    };
+}
+
+fn year_module(existing: &str, day: u8) -> String {
+    let re = Regex::new(r"(?m)^pub mod (day\d{2});$").unwrap();
+    let mut days = re
+        .captures_iter(existing)
+        .map(|caps| caps[1].to_string())
+        .collect::<Vec<String>>();
+    days.push(format!("day{:02}", day));
+    days.sort();
+    days.dedup();
+
+    let mut out = String::from(
+        "// Each day is also built as its own binary, which is the only place its
+// `main` is used.
+#![allow(dead_code)]
+
+use crate::registry::Entry;
+
+",
+    );
+    for d in &days {
+        out.push_str(&format!("#[path = \"{}/main.rs\"]\npub mod {};\n\n", d, d));
+    }
+    out.push_str("pub fn days() -> Vec<Entry> {\n    vec![\n");
+    for d in &days {
+        out.push_str(&format!("        Entry::of::<{}::Day>(),\n", d));
+    }
+    out.push_str("    ]\n}\n");
+    return out;
+}
+
+fn add_year_to_lib(lib: &str, year: u16) -> Result<String, String> {
+    let block = format!("#[path = \"{}/mod.rs\"]\npub mod y{};\n", year, year);
+    let re = Regex::new(r#"(?m)^#\[path = "(\d{4})/mod.rs"\]\npub mod y\d{4};\n"#).unwrap();
+    insert_sorted(lib, &re, &year.to_string(), &block, "\n")
+        .ok_or_else(|| String::from("couldn't find the year modules in src/lib.rs"))
+}
+
+fn add_year_to_registry(registry: &str, year: u16) -> Result<String, String> {
+    let line = format!("    entries.extend(crate::y{}::days());\n", year);
+    let re = Regex::new(r"(?m)^    entries\.extend\(crate::y(\d{4})::days\(\)\);\n").unwrap();
+    insert_sorted(registry, &re, &year.to_string(), &line, "").ok_or_else(|| {
+        String::from("couldn't find where years are registered in src/registry/mod.rs")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[package]
+name = \"advent-of-code\"
+
+[[bin]]
+name = \"aoc\"
+path = \"src/bin/aoc.rs\"
+
+[[bin]]
+name = \"2015day01\"
+path = \"src/2015/day01/main.rs\"
+
+[[bin]]
+name = \"2023day21\"
+path = \"src/2023/day21/main.rs\"
+";
+
+    const LIB: &str = "pub mod utils;
+
+#[path = \"2015/mod.rs\"]
+pub mod y2015;
+
+#[path = \"2023/mod.rs\"]
+pub mod y2023;
+";
+
+    const REGISTRY: &str = "pub fn all() -> Vec<Entry> {
+    let mut entries = Vec::new();
+    entries.extend(crate::y2015::days());
+    entries.extend(crate::y2023::days());
+    entries.sort_by_key(|e| (e.year, e.day));
+    return entries;
+}
+";
+
+    #[test]
+    fn test_add_bin() {
+        let manifest = add_bin(MANIFEST, 2022, 3).unwrap();
+        assert!(manifest.contains(
+            "path = \"src/2015/day01/main.rs\"
+
+[[bin]]
+name = \"2022day03\"
+path = \"src/2022/day03/main.rs\"
+
+[[bin]]
+name = \"2023day21\""
+        ));
+
+        let manifest = add_bin(MANIFEST, 2024, 3).unwrap();
+        assert!(manifest.ends_with(
+            "path = \"src/2023/day21/main.rs\"
+
+[[bin]]
+name = \"2024day03\"
+path = \"src/2024/day03/main.rs\"
+"
+        ));
+
+        assert!(add_bin(MANIFEST, 2023, 21).is_err());
+    }
+
+    #[test]
+    fn test_year_module() {
+        let module = year_module(&year_module("", 5), 3);
+        assert!(module.contains("pub mod day03;\n\n#[path = \"day05/main.rs\"]\npub mod day05;"));
+        assert!(module.contains("Entry::of::<day03::Day>(),\n        Entry::of::<day05::Day>(),"));
+    }
+
+    #[test]
+    fn test_add_year() {
+        let lib = add_year_to_lib(LIB, 2024).unwrap();
+        assert!(lib.ends_with("pub mod y2023;\n\n#[path = \"2024/mod.rs\"]\npub mod y2024;\n"));
+        let lib = add_year_to_lib(LIB, 2016).unwrap();
+        assert!(
+            lib.contains("pub mod y2015;\n\n#[path = \"2016/mod.rs\"]\npub mod y2016;\n\n#[path")
+        );
+        assert!(add_year_to_lib("pub mod utils;\n", 2024).is_err());
+
+        let registry = add_year_to_registry(REGISTRY, 2017).unwrap();
+        assert!(registry.contains(
+            "crate::y2015::days());\n    entries.extend(crate::y2017::days());\n    entries.extend(crate::y2023"
+        ));
+        let registry = add_year_to_registry(REGISTRY, 2024).unwrap();
+        assert!(registry.contains(
+            "crate::y2023::days());\n    entries.extend(crate::y2024::days());\n    entries.sort"
+        ));
+        assert!(add_year_to_registry("pub fn all() {}\n", 2024).is_err());
+    }
+
+    /// A checkout with 2015 day 1 and 2023 day 21 in it.
+    fn fake_repo() -> PathBuf {
+        let root = std::env::temp_dir().join("aoc_test_new_day_plan");
+        let _ = fs::remove_dir_all(&root);
+        for (path, contents) in [
+            ("Cargo.toml", MANIFEST),
+            ("src/lib.rs", LIB),
+            ("src/registry/mod.rs", REGISTRY),
+            ("src/2015/mod.rs", &year_module("", 1)),
+            ("src/2015/day01/main.rs", ""),
+            ("src/2023/mod.rs", &year_module("", 21)),
+            ("src/2023/day21/main.rs", ""),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        return root;
+    }
+
+    #[test]
+    fn test_plan() {
+        let root = fake_repo();
+        assert!(plan(&root, 2023, 21).is_err());
+
+        let paths = |files: &Vec<(PathBuf, String)>| {
+            files
+                .iter()
+                .map(|(p, _)| {
+                    p.strip_prefix(&root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect::<Vec<String>>()
+        };
+
+        // A year that's already there only needs its module updating.
+        let files = plan(&root, 2023, 9).unwrap();
+        assert_eq!(
+            paths(&files),
+            [
+                "src/2023/day09/main.rs",
+                "Cargo.toml",
+                "src/2023/mod.rs",
+                "inputs/2023/day09.txt",
+                "examples/2023/day09/1.toml",
+                "examples/2023/day09/1.txt",
+            ]
+        );
+
+        let files = plan(&root, 2024, 3).unwrap();
+        assert_eq!(
+            paths(&files),
+            [
+                "src/2024/day03/main.rs",
+                "Cargo.toml",
+                "src/2024/mod.rs",
+                "src/lib.rs",
+                "src/registry/mod.rs",
+                "inputs/2024/day03.txt",
+                "examples/2024/day03/1.toml",
+                "examples/2024/day03/1.txt",
+            ]
+        );
+        let lib = &files[3].1;
+        assert!(lib.ends_with("#[path = \"2024/mod.rs\"]\npub mod y2024;\n"));
+    }
+}
@@ -0,0 +1,43 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Anything that can go wrong between finding a puzzle input and handing the
+/// parsed form to a day's parts. Line numbers start at 1.
+#[derive(Debug)]
+pub enum AocError {
+    /// The input couldn't be opened or read.
+    Io { path: String, source: io::Error },
+    /// The input isn't valid UTF-8 at this line.
+    Utf8 { line: usize },
+    /// A line didn't look the way the day expected.
+    Parse { line: usize, message: String },
+}
+
+impl AocError {
+    pub fn parse<M: fmt::Display>(line: usize, message: M) -> Self {
+        AocError::Parse {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "couldn't read {}: {}", path, source),
+            AocError::Utf8 { line } => write!(f, "line {}: not valid UTF-8", line),
+            AocError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
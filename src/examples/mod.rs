@@ -0,0 +1,70 @@
+use crate::answers::Answer;
+use crate::solution::Solution;
+use crate::utils::input::read_lines;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Generates a `#[test]` for every `.txt` fixture in `examples/<dir>/`, e.g.
+/// `example_tests!(Day, "2023/day05")`.
+///
+/// Each fixture's expected answers sit beside it in a `.toml` file with the
+/// same name:
+///
+/// ```toml
+/// part1 = 35
+/// part2 = 46
+/// ```
+///
+/// A part that's left out isn't checked, which is handy when an example only
+/// covers one part or the day's constants don't fit it.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:ty, $dir:literal) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            fn check(path: &str) {
+                $crate::examples::check::<$day>(path);
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples/", $dir, ".rs"));
+        }
+    };
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Expected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Runs a day on a fixture and compares it against the answers beside it.
+pub fn check<S: Solution>(path: &str) {
+    let input = S::parse(&lines(path)).unwrap_or_else(|e| panic!("{}: {}", path, e));
+
+    let answers = Path::new(path).with_extension("toml");
+    let expected: Expected = match fs::read_to_string(&answers) {
+        Ok(contents) => {
+            toml::from_str(&contents).unwrap_or_else(|e| panic!("{}: {}", answers.display(), e))
+        }
+        Err(_) => Expected::default(),
+    };
+
+    if let Some(want) = expected.part1 {
+        let got = S::part1(&input).to_string();
+        assert_eq!(got, want.to_string(), "part 1 of {}", path);
+    }
+    if let Some(want) = expected.part2 {
+        let got = S::part2(&input).to_string();
+        assert_eq!(got, want.to_string(), "part 2 of {}", path);
+    }
+}
+
+/// A fixture's lines, for tests that need to drive a day's helpers directly,
+/// e.g. `lines("examples/2023/day21/1.txt")`.
+pub fn lines<P: AsRef<Path>>(path: P) -> Vec<String> {
+    let path = path.as_ref();
+    read_lines(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
@@ -1,6 +1,60 @@
+// Explicit `return`s are the house style.
+#![allow(clippy::needless_return)]
+
+// Day solutions refer to the library by name, including when they are
+// compiled into it below.
+extern crate self as advent_of_code;
+
 pub mod utils {
 
+    pub mod cycle;
+
+    pub mod geometry;
+
+    pub mod grid;
+
     pub mod input;
 
+    pub mod interval;
+
+    pub mod math;
+
+    pub mod memo;
+
+    pub mod ocr;
+
+    pub mod parse;
+
     pub mod point;
+
+    pub mod search;
+
+    pub mod viz;
 }
+
+pub mod answers;
+
+pub mod bench;
+
+pub mod error;
+
+pub mod examples;
+
+pub mod registry;
+
+pub mod solution;
+
+#[path = "2015/mod.rs"]
+pub mod y2015;
+
+#[path = "2016/mod.rs"]
+pub mod y2016;
+
+#[path = "2020/mod.rs"]
+pub mod y2020;
+
+#[path = "2022/mod.rs"]
+pub mod y2022;
+
+#[path = "2023/mod.rs"]
+pub mod y2023;
@@ -0,0 +1,125 @@
+#![allow(clippy::type_complexity)]
+
+use crate::error::AocError;
+use crate::solution::Solution;
+use std::any::Any;
+
+/// A type-erased [`Solution`], so days with different input and answer types
+/// can sit in the same list.
+#[derive(Copy, Clone)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    parse: fn(&[String]) -> Result<Box<dyn Any>, AocError>,
+    part1: fn(&dyn Any) -> String,
+    part2: fn(&dyn Any) -> String,
+    diagnostics: fn(&dyn Any) -> Vec<String>,
+}
+
+impl Entry {
+    pub fn of<S>() -> Self
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            parse: |lines| Ok(Box::new(S::parse(lines)?)),
+            part1: |input| S::part1(downcast::<S>(input)).to_string(),
+            part2: |input| S::part2(downcast::<S>(input)).to_string(),
+            diagnostics: |input| S::diagnostics(downcast::<S>(input)),
+        }
+    }
+
+    pub fn parse(&self, lines: &[String]) -> Result<Box<dyn Any>, AocError> {
+        (self.parse)(lines)
+    }
+
+    /// Panics if `input` didn't come from this entry's `parse`.
+    pub fn part1(&self, input: &dyn Any) -> String {
+        (self.part1)(input)
+    }
+
+    /// Panics if `input` didn't come from this entry's `parse`.
+    pub fn part2(&self, input: &dyn Any) -> String {
+        (self.part2)(input)
+    }
+
+    /// Panics if `input` didn't come from this entry's `parse`.
+    pub fn diagnostics(&self, input: &dyn Any) -> Vec<String> {
+        (self.diagnostics)(input)
+    }
+
+    pub fn solve(&self, lines: &[String]) -> Result<(String, String), AocError> {
+        let input = self.parse(lines)?;
+        return Ok((self.part1(input.as_ref()), self.part2(input.as_ref())));
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref::<S::Input>()
+        .expect("input was parsed by a different day")
+}
+
+/// Every registered day, ordered by year and then day.
+pub fn all() -> Vec<Entry> {
+    let mut entries = Vec::new();
+    entries.extend(crate::y2015::days());
+    entries.extend(crate::y2016::days());
+    entries.extend(crate::y2020::days());
+    entries.extend(crate::y2022::days());
+    entries.extend(crate::y2023::days());
+    entries.sort_by_key(|e| (e.year, e.day));
+    return entries;
+}
+
+pub fn year(year: u16) -> Vec<Entry> {
+    all().into_iter().filter(|e| e.year == year).collect()
+}
+
+pub fn get(year: u16, day: u8) -> Option<Entry> {
+    all().into_iter().find(|e| e.year == year && e.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let entries = all();
+        for pair in entries.windows(2) {
+            assert!(
+                (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day),
+                "duplicate or unordered entry {}/{}",
+                pair[1].year,
+                pair[1].day
+            );
+        }
+
+        assert!(get(2023, 9).is_some());
+        assert!(get(2023, 26).is_none());
+        assert!(year(2015).iter().all(|e| e.year == 2015));
+    }
+
+    #[test]
+    fn test_solve() {
+        let lines = vec![
+            String::from("0 3 6 9 12 15"),
+            String::from("1 3 6 10 15 21"),
+            String::from("10 13 16 21 30 45"),
+        ];
+        let entry = get(2023, 9).unwrap();
+        let (pt1, pt2) = entry.solve(&lines).unwrap();
+        assert_eq!(pt1, "114");
+        assert_eq!(pt2, "2");
+        assert!(entry
+            .diagnostics(entry.parse(&lines).unwrap().as_ref())
+            .is_empty());
+    }
+}
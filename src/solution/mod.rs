@@ -0,0 +1,128 @@
+use crate::answers;
+use crate::bench;
+use crate::error::AocError;
+use crate::registry::Entry;
+use crate::utils::input::Source;
+use crate::utils::viz::{ImageFormat, Target, Visualizer};
+use clap::Parser;
+use std::fmt;
+use std::process;
+use std::time::Duration;
+
+/// A single day's puzzle.
+///
+/// `parse` turns the raw input lines into whatever the day works on, or says
+/// which line it couldn't make sense of. Each part computes its answer from
+/// that without touching the filesystem, so a day can be driven from a binary,
+/// a test or the registry alike.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input;
+    type Part1: fmt::Display;
+    type Part2: fmt::Display;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Draws the puzzle being worked through, frame by frame, for
+    /// `--visualize`. Most days don't have anything to show.
+    fn visualize(_input: &Self::Input, _viz: &mut Visualizer) {}
+
+    /// Anything else worth knowing about the input that isn't an answer, one
+    /// line each, printed after the answers on every run.
+    fn diagnostics(_input: &Self::Input) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Answer for a part that hasn't been solved (yet).
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Unsolved;
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// Input file, or `-` for stdin. Defaults to inputs/<year>/day<day>.txt.
+    #[arg(short, long, conflicts_with = "data")]
+    pub input: Option<String>,
+
+    /// The puzzle input itself, instead of a file.
+    #[arg(short, long)]
+    pub data: Option<String>,
+
+    /// Time parsing and each part over N runs instead of printing answers.
+    #[arg(long, value_name = "N")]
+    pub bench: Option<usize>,
+
+    /// Draw the puzzle before answering it, if the day knows how: `-` (the
+    /// default) animates it in the terminal, a `.gif` path saves an animation
+    /// and anything else is a directory to save each frame in.
+    #[arg(long, value_name = "OUT", num_args = 0..=1, default_missing_value = "-")]
+    pub visualize: Option<String>,
+
+    /// What to save frames as when `--visualize` is given a directory.
+    #[arg(long, value_enum, default_value_t = ImageFormat::Png)]
+    pub frame_format: ImageFormat,
+
+    /// How long each frame is shown for, in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 100)]
+    pub delay: u64,
+}
+
+/// Entry point shared by every day's binary.
+pub fn run<S>()
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    let args = Args::parse();
+    let source = Source::resolve(S::YEAR, S::DAY, args.input.as_deref(), args.data.as_deref());
+    let lines = source.read().unwrap_or_else(|err| exit(err));
+
+    if let Some(iterations) = args.bench {
+        let report = bench::bench(&Entry::of::<S>(), &lines, iterations);
+        print!(
+            "{}",
+            bench::table(&[report.unwrap_or_else(|err| exit(err))])
+        );
+        return;
+    }
+
+    let input = S::parse(&lines).unwrap_or_else(|err| exit(err));
+
+    if let Some(out) = args.visualize {
+        let mut viz = Visualizer::new(Target::parse(&out, args.frame_format))
+            .with_delay(Duration::from_millis(args.delay));
+        S::visualize(&input, &mut viz);
+        if viz.frames() == 0 {
+            eprintln!("{} day {} doesn't draw anything", S::YEAR, S::DAY);
+        }
+        viz.finish().unwrap_or_else(|err| exit(err));
+    }
+
+    let part1 = S::part1(&input).to_string();
+    println!("Part 1: {}", part1);
+    answers::report(S::YEAR, S::DAY, 1, &part1);
+
+    let part2 = S::part2(&input).to_string();
+    println!("Part 2: {}", part2);
+    answers::report(S::YEAR, S::DAY, 2, &part2);
+
+    for line in S::diagnostics(&input) {
+        println!("{}", line);
+    }
+}
+
+fn exit(err: AocError) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+/// A simulation that settles into a loop: after `start` steps it repeats
+/// every `period` steps.
+///
+/// `states[i]` is the state after `i` steps, up to the first time the loop
+/// comes back around.
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub period: usize,
+    pub states: Vec<S>,
+}
+
+/// Steps from `initial` until a state comes up a second time. Every state is
+/// kept, so it should be cheap to clone and hash (e.g. a summary of the parts
+/// of the simulation that matter going forward).
+pub fn find<S, F>(initial: S, step: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    find_within(initial, usize::MAX, step).expect("ran out of steps")
+}
+
+/// The same as `find`, giving up with `None` after `max_steps` steps for
+/// simulations whose loop might be too long to bother with.
+pub fn find_within<S, F>(initial: S, max_steps: usize, mut step: F) -> Option<Cycle<S>>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![initial.clone()];
+    seen.insert(initial, 0);
+    while states.len() <= max_steps {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            return Some(Cycle {
+                start,
+                period: states.len() - start,
+                states,
+            });
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+    return None;
+}
+
+impl<S> Cycle<S> {
+    /// The step before the loop first comes back around that has the same
+    /// state as step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        return self.start + (n - self.start) % self.period;
+    }
+
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.index(n)]
+    }
+
+    /// Carries something that builds up over the steps (e.g. a tower's height)
+    /// out to step `n`, given `values[i]` after `i` steps. `values` has to go
+    /// at least one whole period past the start of the loop.
+    pub fn extrapolate<T>(&self, values: &[T], n: usize) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+    {
+        let end = self.start + self.period;
+        assert!(
+            values.len() > end,
+            "need {} values to extrapolate, got {}",
+            end + 1,
+            values.len()
+        );
+        if n < values.len() {
+            return values[n];
+        }
+
+        let loops = (n - self.start) / self.period;
+        let gained = values[end] - values[self.start];
+        let loops = T::try_from(loops).unwrap_or_else(|_| panic!("{} loops overflow", loops));
+        return values[self.index(n)] + gained * loops;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let cycle = find(0, |&x| if x == 4 { 2 } else { x + 1 });
+        assert_eq!((cycle.start, cycle.period), (2, 3));
+        assert_eq!(cycle.states, vec![0, 1, 2, 3, 4]);
+
+        let tests = [(0, 0), (4, 4), (5, 2), (7, 4), (1000000000, 4)];
+        for (n, want) in tests {
+            assert_eq!(*cycle.state_at(n), want, "after {} steps", n);
+        }
+
+        let cycle = find(7, |&x| x);
+        assert_eq!((cycle.start, cycle.period), (0, 1));
+
+        let mut steps = 0;
+        let counter = |&x: &i32| {
+            steps += 1;
+            x + 1
+        };
+        assert!(find_within(0, 10, counter).is_none());
+        assert_eq!(steps, 10);
+        assert_eq!(find_within(0, 5, |&x| (x + 1) % 5).unwrap().period, 5);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let cycle = find(0, |&x| if x == 4 { 2 } else { x + 1 });
+        // Adds up the states as it goes: after 2 steps, every 3 more add 9.
+        let mut values: Vec<i64> = vec![0];
+        let mut state = 0;
+        for _ in 0..20 {
+            state = if state == 4 { 2 } else { state + 1 };
+            values.push(values.last().unwrap() + state);
+        }
+
+        for n in 0..values.len() {
+            assert_eq!(cycle.extrapolate(&values[..6], n), values[n], "at {}", n);
+        }
+        assert_eq!(
+            cycle.extrapolate::<usize>(&[0, 1, 3, 6, 10, 12], 1000000001),
+            3000000000
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Polygons given as their corners in order, where the last corner joins back
+//! up with the first. Corners are on the same y-down grid as `Point`, so
+//! "clockwise" means clockwise as drawn in the puzzle input.
+
+use crate::utils::math;
+use crate::utils::point::Point;
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Where a point is compared to a polygon.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+fn edges(corners: &[Point<i64>]) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+    corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn cross(a: Point<i64>, b: Point<i64>) -> i64 {
+    a.x * b.y - b.x * a.y
+}
+
+/// Twice the area by the shoelace formula, positive when the corners go
+/// clockwise. Doubling keeps it a whole number for corners on the grid.
+pub fn signed_double_area(corners: &[Point<i64>]) -> i64 {
+    edges(corners).map(|(a, b)| cross(a, b)).sum()
+}
+
+/// Twice the area, whichever way round the corners go.
+pub fn double_area(corners: &[Point<i64>]) -> i64 {
+    signed_double_area(corners).abs()
+}
+
+/// The area, rounded down if it's a half.
+pub fn area(corners: &[Point<i64>]) -> i64 {
+    double_area(corners) / 2
+}
+
+/// Which way round the corners go, or `None` if the polygon is flat.
+pub fn winding(corners: &[Point<i64>]) -> Option<Winding> {
+    match signed_double_area(corners) {
+        0 => None,
+        a if a > 0 => Some(Winding::Clockwise),
+        _ => Some(Winding::CounterClockwise),
+    }
+}
+
+/// How many grid points the edges go through.
+pub fn boundary_points(corners: &[Point<i64>]) -> i64 {
+    edges(corners)
+        .map(|(a, b)| math::gcd(b.x - a.x, b.y - a.y))
+        .sum()
+}
+
+/// How many grid points are strictly inside, by Pick's theorem:
+/// `area = interior + boundary / 2 - 1`.
+pub fn interior_points(corners: &[Point<i64>]) -> i64 {
+    (double_area(corners) - boundary_points(corners) + 2) / 2
+}
+
+/// Every grid point inside or on the edges, e.g. the tiles a loop of tiles
+/// covers.
+pub fn enclosed_points(corners: &[Point<i64>]) -> i64 {
+    interior_points(corners) + boundary_points(corners)
+}
+
+fn on_segment(p: Point<i64>, a: Point<i64>, b: Point<i64>) -> bool {
+    cross(b - a, p - a) == 0
+        && a.x.min(b.x) <= p.x
+        && p.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= p.y
+        && p.y <= a.y.max(b.y)
+}
+
+/// Whether `p` is inside, on an edge of, or outside the polygon, which can
+/// be any shape as long as its edges don't cross.
+pub fn locate(corners: &[Point<i64>], p: Point<i64>) -> Location {
+    let mut inside = false;
+    for (a, b) in edges(corners) {
+        if on_segment(p, a, b) {
+            return Location::Boundary;
+        }
+        // Count the edges crossing a ray going east from `p`. Each edge only
+        // includes the end with the bigger y, so a corner the ray goes
+        // through is only counted when the edges carry on across it.
+        if (a.y > p.y) != (b.y > p.y) {
+            let side = cross(b - a, p - a);
+            if (side > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        return Location::Inside;
+    }
+    return Location::Outside;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(corners: &[(i64, i64)]) -> Vec<Point<i64>> {
+        corners.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_area() {
+        // A 4x3 rectangle, going clockwise on screen.
+        let rect = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(signed_double_area(&rect), 24);
+        assert_eq!(area(&rect), 12);
+        assert_eq!(winding(&rect), Some(Winding::Clockwise));
+        assert_eq!(boundary_points(&rect), 14);
+        assert_eq!(interior_points(&rect), 6);
+        assert_eq!(enclosed_points(&rect), 20);
+
+        let mut reversed = rect.clone();
+        reversed.reverse();
+        assert_eq!(signed_double_area(&reversed), -24);
+        assert_eq!(winding(&reversed), Some(Winding::CounterClockwise));
+        assert_eq!(interior_points(&reversed), 6);
+
+        // A triangle with a diagonal edge and half an area.
+        let triangle = polygon(&[(0, 0), (3, 0), (0, 3)]);
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+
+        assert_eq!(winding(&polygon(&[(0, 0), (1, 1), (2, 2)])), None);
+    }
+
+    #[test]
+    fn test_locate() {
+        // A U shape:
+        // ###.###
+        // #.#.#.#
+        // #.###.#
+        // #.....#
+        // #######
+        let u = polygon(&[
+            (0, 0),
+            (2, 0),
+            (2, 2),
+            (4, 2),
+            (4, 0),
+            (6, 0),
+            (6, 4),
+            (0, 4),
+        ]);
+        let tests = [
+            ((1, 1), Location::Inside),
+            ((3, 3), Location::Inside),
+            ((5, 1), Location::Inside),
+            ((3, 1), Location::Outside),
+            ((3, 0), Location::Outside),
+            ((-1, 2), Location::Outside),
+            ((7, 0), Location::Outside),
+            ((0, 0), Location::Boundary),
+            ((3, 2), Location::Boundary),
+            ((6, 3), Location::Boundary),
+        ];
+        for ((x, y), want) in tests {
+            assert_eq!(locate(&u, Point::new(x, y)), want, "({},{})", x, y);
+        }
+
+        // Counting the points by hand agrees with Pick's theorem.
+        let mut inside = 0;
+        for x in -1..8 {
+            for y in -1..6 {
+                if locate(&u, Point::new(x, y)) == Location::Inside {
+                    inside += 1;
+                }
+            }
+        }
+        assert_eq!(inside, interior_points(&u));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::error::AocError;
+use crate::utils::point::Point;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular grid stored row by row in one `Vec`.
+///
+/// Points are `x` = column and `y` = row, with `(0,0)` at the top left like
+/// the puzzle input. Lookups take any integer `Point`, so negative or
+/// out-of-range neighbors just come back as `None`.
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` doesn't hold exactly `width * height` values.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cells don't fill the grid");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from the input, one row per line, turning each character
+    /// into a cell with `f`. Every line has to be the same length.
+    pub fn parse<F>(lines: &[String], f: F) -> Result<Self, AocError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let before = cells.len();
+            for c in line.chars() {
+                match f(c) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(AocError::parse(i + 1, format!("unexpected {:?}", c))),
+                }
+            }
+            if cells.len() - before != width {
+                return Err(AocError::parse(
+                    i + 1,
+                    format!("expected {} cells, got {}", width, cells.len() - before),
+                ));
+            }
+        }
+        return Ok(Self::from_vec(width, lines.len(), cells));
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of<I: TryInto<usize>>(&self, p: Point<I>) -> Option<usize> {
+        let x = p.x.try_into().ok()?;
+        let y = p.y.try_into().ok()?;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        return Some(y * self.width + x);
+    }
+
+    fn point_of(&self, i: usize) -> Point<usize> {
+        Point {
+            x: i % self.width,
+            y: i / self.width,
+        }
+    }
+
+    pub fn contains<I: TryInto<usize>>(&self, p: Point<I>) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    pub fn get<I: TryInto<usize>>(&self, p: Point<I>) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<I: TryInto<usize>>(&mut self, p: Point<I>) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> + '_ {
+        (0..self.cells.len()).map(|i| self.point_of(i))
+    }
+
+    /// The first point, row by row, whose cell matches.
+    pub fn position<F: Fn(&T) -> bool>(&self, f: F) -> Option<Point<usize>> {
+        self.cells.iter().position(f).map(|i| self.point_of(i))
+    }
+
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_vec(width, height, vec![fill; width * height])
+    }
+
+    /// Builds a grid of the given size by asking `f` for each new point.
+    fn rearrange<F>(&self, width: usize, height: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> Point<usize>,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self[f(x, y)].clone());
+            }
+        }
+        return Self::from_vec(width, height, cells);
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.rearrange(self.height, self.width, |x, y| Point { x: y, y: x })
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        self.rearrange(self.height, self.width, |x, y| Point { x: y, y: h - 1 - x })
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width;
+        self.rearrange(self.height, self.width, |x, y| Point { x: w - 1 - y, y: x })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.rearrange(self.width, self.height, |x, y| Point { x: w - 1 - x, y })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.rearrange(self.width, self.height, |x, y| Point { x, y: h - 1 - y })
+    }
+}
+
+/// Panics if the point is out of bounds.
+impl<T, I: TryInto<usize>> Index<Point<I>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<I>) -> &T {
+        let i = self.index_of(p).expect("point is outside the grid");
+        &self.cells[i]
+    }
+}
+
+/// Panics if the point is out of bounds.
+impl<T, I: TryInto<usize>> IndexMut<Point<I>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<I>) -> &mut T {
+        let i = self.index_of(p).expect("point is outside the grid");
+        &mut self.cells[i]
+    }
+}
+
+/// One line per row, with no separators between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(lines: &[&str]) -> Grid<char> {
+        let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
+        Grid::parse(&lines, Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.to_string(), "abc\ndef");
+
+        let digits = Grid::parse(&[String::from("123")], |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.row(0), &[1, 2, 3]);
+
+        let lines = vec![String::from("ab"), String::from("abc")];
+        assert!(matches!(
+            Grid::parse(&lines, Some),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        let lines = vec![String::from("12"), String::from("1x")];
+        assert!(matches!(
+            Grid::parse(&lines, |c| c.to_digit(10)),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_get() {
+        let mut g = grid(&["abc", "def"]);
+        let tests = [
+            (Point { x: 0, y: 0 }, Some('a')),
+            (Point { x: 2, y: 1 }, Some('f')),
+            (Point { x: 3, y: 1 }, None),
+            (Point { x: 0, y: 2 }, None),
+            (Point { x: -1, y: 0 }, None),
+        ];
+        for (p, want) in tests {
+            assert_eq!(g.get(p).copied(), want, "at {}", p);
+        }
+
+        *g.get_mut(Point { x: 1, y: 1 }).unwrap() = 'E';
+        g[Point { x: 0, y: 1_usize }] = 'D';
+        assert_eq!(g.to_string(), "abc\nDEf");
+        assert_eq!(g.position(|c| *c == 'E'), Some(Point { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!(
+            g.columns()
+                .map(|col| col.collect::<String>())
+                .collect::<Vec<String>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(
+            g.rows()
+                .map(|r| r.iter().collect())
+                .collect::<Vec<String>>(),
+            vec!["abc", "def"]
+        );
+        assert_eq!(g.iter().nth(4), Some((Point { x: 1, y: 1 }, &'e')));
+        assert_eq!(g.points().last(), Some(Point { x: 2, y: 1 }));
+    }
+
+    #[test]
+    fn test_rearrange() {
+        let g = grid(&["abc", "def"]);
+        let tests = [
+            (g.transpose(), "ad\nbe\ncf"),
+            (g.rotate_cw(), "da\neb\nfc"),
+            (g.rotate_ccw(), "cf\nbe\nad"),
+            (g.flip_horizontal(), "cba\nfed"),
+            (g.flip_vertical(), "def\nabc"),
+            (
+                g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(),
+                "abc\ndef",
+            ),
+        ];
+        for (got, want) in tests {
+            assert_eq!(got.to_string(), want);
+        }
+    }
+}
@@ -1,14 +1,151 @@
+use crate::error::AocError;
+use std::env;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-pub fn read_lines<P>(filename: P) -> Vec<String>
+/// Overrides the `inputs` directory that puzzle inputs are cached in.
+pub const INPUTS_DIR_ENV: &str = "AOC_INPUTS";
+
+/// Where a day's puzzle input comes from.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Source {
+    Stdin,
+    /// The input itself, e.g. passed with `--data`.
+    Data(String),
+    File(PathBuf),
+}
+
+impl Source {
+    /// `-` means stdin. Without a path or data, falls back to the cached
+    /// input for the day.
+    pub fn resolve(year: u16, day: u8, input: Option<&str>, data: Option<&str>) -> Self {
+        match (input, data) {
+            (_, Some(data)) => Source::Data(data.to_owned()),
+            (Some("-"), None) => Source::Stdin,
+            (Some(path), None) => Source::File(PathBuf::from(path)),
+            (None, None) => Source::File(cached_path(year, day)),
+        }
+    }
+
+    pub fn read(&self) -> Result<Vec<String>, AocError> {
+        match self {
+            Source::Stdin => read_from(io::stdin().lock(), "stdin"),
+            Source::Data(data) => read_from(data.as_bytes(), "data"),
+            Source::File(path) => read_lines(path),
+        }
+    }
+}
+
+/// `inputs/<year>/day<day>.txt`, under `$AOC_INPUTS` instead if it's set.
+pub fn cached_path(year: u16, day: u8) -> PathBuf {
+    let dir = env::var_os(INPUTS_DIR_ENV).unwrap_or_else(|| "inputs".into());
+    return Path::new(&dir)
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day));
+}
+
+/// Reads every line of a file, without the trailing newline (or `\r\n`).
+pub fn read_lines<P>(filename: P) -> Result<Vec<String>, AocError>
 where
     P: AsRef<Path>,
 {
-    return io::BufReader::new(File::open(filename).expect("where is the file"))
-        .lines()
-        .filter(|x| x.is_ok())
-        .map(|x| x.expect("bad lines should be filtered"))
-        .collect::<Vec<String>>();
+    let path = filename.as_ref().display().to_string();
+    let file = File::open(&filename).map_err(|source| AocError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    return read_from(io::BufReader::new(file), &path);
+}
+
+fn read_from<R: BufRead>(reader: R, name: &str) -> Result<Vec<String>, AocError> {
+    let io_err = |source| AocError::Io {
+        path: name.to_owned(),
+        source,
+    };
+
+    return reader
+        .split(b'\n')
+        .enumerate()
+        .map(|(i, line)| {
+            let mut bytes = line.map_err(io_err)?;
+            if bytes.last() == Some(&b'\r') {
+                bytes.pop();
+            }
+            String::from_utf8(bytes).map_err(|_| AocError::Utf8 { line: i + 1 })
+        })
+        .collect();
+}
+
+/// Parses `s`, which came from line `line` (counting from 1) of the input.
+pub fn parse_at<T>(line: usize, s: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.parse::<T>()
+        .map_err(|e| AocError::parse(line, format!("{:?}: {}", s, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_read_lines() {
+        let dir = std::env::temp_dir().join("aoc_test_read_lines");
+        fs::create_dir_all(&dir).unwrap();
+
+        let good = dir.join("good.txt");
+        fs::write(&good, "1 2\r\n3 4\n\n5").unwrap();
+        assert_eq!(read_lines(&good).unwrap(), vec!["1 2", "3 4", "", "5"]);
+
+        let bad = dir.join("bad.txt");
+        fs::write(&bad, b"ok\nstill ok\n\xff\xfe\n").unwrap();
+        assert!(matches!(read_lines(&bad), Err(AocError::Utf8 { line: 3 })));
+
+        assert!(matches!(
+            read_lines(dir.join("missing.txt")),
+            Err(AocError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_source() {
+        let tests = [
+            ((Some("-"), None), Source::Stdin),
+            (
+                (Some("in.txt"), None),
+                Source::File(PathBuf::from("in.txt")),
+            ),
+            ((None, Some("1\n2")), Source::Data(String::from("1\n2"))),
+        ];
+        for ((input, data), want) in tests {
+            assert_eq!(Source::resolve(2023, 5, input, data), want);
+        }
+
+        assert_eq!(
+            Source::resolve(2023, 5, None, None),
+            Source::File(cached_path(2023, 5))
+        );
+        assert!(cached_path(2023, 5).ends_with("2023/day05.txt"));
+
+        let lines = Source::Data(String::from("a b\r\nc\n")).read().unwrap();
+        assert_eq!(lines, vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn test_parse_at() {
+        assert_eq!(parse_at::<i64>(1, "-12").unwrap(), -12);
+        match parse_at::<i64>(7, "x1") {
+            Err(AocError::Parse { line, message }) => {
+                assert_eq!(line, 7);
+                assert_eq!(message, "\"x1\": invalid digit found in string");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
 }
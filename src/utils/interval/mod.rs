@@ -0,0 +1,581 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// The integer types intervals can be made of.
+pub trait Bound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>> Bound for T {}
+
+/// The values from `start` up to but not including `end`. It's empty when
+/// `end <= start`.
+///
+/// This is the half-open kind, which is what `IntervalSet` and `Piecewise`
+/// are made of. See `Inclusive` for the kind with both ends in it, which
+/// converts to and from this one. `Display` prints the half-open form, e.g.
+/// `[2,5)` for the values 2 through 4.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    /// One past the biggest value.
+    pub end: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{})", self.start, self.end)
+    }
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The values from `first` through `last`, as `[first, last + 1)`. This is
+    /// short for `Interval::from(Inclusive::new(first, last))`.
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::from(Inclusive::new(first, last))
+    }
+
+    /// The same values with both ends included, or `None` if there aren't
+    /// any, since an empty interval has no last value to end on.
+    pub fn to_inclusive(&self) -> Option<Inclusive<T>> {
+        self.last().map(|last| Inclusive::new(self.start, last))
+    }
+
+    /// The biggest value in the interval, if there is one. This is the
+    /// inclusive end, so `Interval::inclusive(2, 4).last()` is `Some(4)`.
+    pub fn last(&self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        return Some(self.end - T::from(1));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            return T::from(0);
+        }
+        return self.end - self.start;
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every value in `other` is also in this one.
+    pub fn covers(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The values in both, or `None` if they don't share any.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let res = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        if res.is_empty() {
+            return None;
+        }
+        return Some(res);
+    }
+
+    /// The values left over after taking out `other`: up to one piece on
+    /// either side of it.
+    pub fn difference(&self, other: &Interval<T>) -> Vec<Interval<T>> {
+        if !self.overlaps(other) {
+            if self.is_empty() {
+                return vec![];
+            }
+            return vec![*self];
+        }
+
+        return [
+            Interval::new(self.start, other.start),
+            Interval::new(other.end, self.end),
+        ]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .collect();
+    }
+
+    /// Moves the interval up by `by` (down if it's negative).
+    pub fn shift(&self, by: T) -> Interval<T> {
+        Interval::new(self.start + by, self.end + by)
+    }
+}
+
+/// The values from `first` through `last`, both included, the way puzzles
+/// usually write them (e.g. "2-4"). It's empty when `last < first`.
+///
+/// Unlike `Interval` it never needs a value past `last`, so it can run right
+/// up to the biggest value of `T`. `Display` prints e.g. `[2,4]`.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Inclusive<T> {
+    pub first: T,
+    pub last: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Inclusive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{}]", self.first, self.last)
+    }
+}
+
+impl<T: Bound> Inclusive<T> {
+    pub fn new(first: T, last: T) -> Self {
+        Self { first, last }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.last < self.first
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            return T::from(0);
+        }
+        return self.last - self.first + T::from(1);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.first <= value && value <= self.last
+    }
+
+    /// Whether every value in `other` is also in this one.
+    pub fn covers(&self, other: &Inclusive<T>) -> bool {
+        other.is_empty() || (self.first <= other.first && other.last <= self.last)
+    }
+
+    pub fn overlaps(&self, other: &Inclusive<T>) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The values in both, or `None` if they don't share any.
+    pub fn intersect(&self, other: &Inclusive<T>) -> Option<Inclusive<T>> {
+        let res = Inclusive::new(self.first.max(other.first), self.last.min(other.last));
+        if res.is_empty() {
+            return None;
+        }
+        return Some(res);
+    }
+}
+
+impl<T: Bound> From<Inclusive<T>> for Interval<T> {
+    /// `[first, last + 1)`, or an empty interval at `first` if there's
+    /// nothing in it.
+    fn from(x: Inclusive<T>) -> Self {
+        if x.is_empty() {
+            return Interval::new(x.first, x.first);
+        }
+        return Interval::new(x.first, x.last + T::from(1));
+    }
+}
+
+/// Any number of values kept as the fewest intervals that cover them, sorted
+/// and apart from each other.
+#[derive(Eq, PartialEq, Debug, Clone, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    fn normalize(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|x| !x.is_empty());
+        intervals.sort_by_key(|x| x.start);
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for x in intervals {
+            match merged.last_mut() {
+                // Touching intervals are merged too, since [1,3) and [3,5)
+                // are the same values as [1,5).
+                Some(last) if x.start <= last.end => last.end = last.end.max(x.end),
+                _ => merged.push(x),
+            }
+        }
+        return Self { intervals: merged };
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = Self::normalize(intervals);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::from(0), |total, x| total + x.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|x| x.contains(value))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|x| x.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().and_then(|x| x.last())
+    }
+
+    /// The intervals in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        Self::normalize([&self.intervals[..], &other.intervals[..]].concat())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(both) = a.intersect(&b) {
+                res.push(both);
+            }
+            // Whichever ends first can't overlap anything else in the other.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        return Self { intervals: res };
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = vec![];
+        for a in &self.intervals {
+            let mut left = vec![*a];
+            for b in other.intervals.iter().filter(|b| b.overlaps(a)) {
+                left = left.iter().flat_map(|x| x.difference(b)).collect();
+            }
+            res.append(&mut left);
+        }
+        return Self { intervals: res };
+    }
+
+    /// Moves every value up by `by` (down if it's negative).
+    pub fn shift(&self, by: T) -> IntervalSet<T> {
+        Self {
+            intervals: self.intervals.iter().map(|x| x.shift(by)).collect(),
+        }
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalize(vec![interval])
+    }
+}
+
+impl<T: Bound> From<Inclusive<T>> for IntervalSet<T> {
+    fn from(interval: Inclusive<T>) -> Self {
+        Self::from(Interval::from(interval))
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/// A mapping that moves values by a different offset depending on which
+/// piece they're in, leaving values outside every piece where they are (like
+/// the almanac maps in 2023 day 5). When pieces overlap, the first one added
+/// wins.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct Piecewise<T> {
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Bound> Piecewise<T> {
+    pub fn new() -> Self {
+        Self { pieces: vec![] }
+    }
+
+    /// Moves the values in `source` by `offset`.
+    pub fn add(&mut self, source: Interval<T>, offset: T) {
+        self.pieces.push((source, offset));
+    }
+
+    pub fn apply(&self, value: T) -> T {
+        match self
+            .pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+        {
+            Some((_, offset)) => value + *offset,
+            None => value,
+        }
+    }
+
+    /// Where every value in `values` ends up.
+    pub fn apply_set(&self, values: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = IntervalSet::new();
+        let mut left = values.clone();
+        for (source, offset) in &self.pieces {
+            let source = IntervalSet::from(*source);
+            res = res.union(&left.intersection(&source).shift(*offset));
+            left = left.difference(&source);
+        }
+        return res.union(&left);
+    }
+
+    /// The mapping that applies this one and then `next`, so that
+    /// `self.then(next).apply(x) == next.apply(self.apply(x))`.
+    pub fn then(&self, next: &Piecewise<T>) -> Piecewise<T> {
+        let mut res = Piecewise::new();
+        let mut claimed = IntervalSet::new();
+        for (source, offset) in &self.pieces {
+            let own = IntervalSet::from(*source).difference(&claimed);
+            claimed = claimed.union(&IntervalSet::from(*source));
+
+            // Follow where these values land through `next`, then take them
+            // back to where they started.
+            let mut landed = own.shift(*offset);
+            for (next_source, next_offset) in &next.pieces {
+                let next_source = IntervalSet::from(*next_source);
+                for x in &landed.intersection(&next_source) {
+                    let from = Interval::new(x.start - *offset, x.end - *offset);
+                    res.add(from, *offset + *next_offset);
+                }
+                landed = landed.difference(&next_source);
+            }
+            for x in &landed {
+                res.add(Interval::new(x.start - *offset, x.end - *offset), *offset);
+            }
+        }
+
+        // Values this one leaves alone only go through `next`.
+        for (next_source, next_offset) in &next.pieces {
+            for x in &IntervalSet::from(*next_source).difference(&claimed) {
+                res.add(*x, *next_offset);
+            }
+            claimed = claimed.union(&IntervalSet::from(*next_source));
+        }
+        return res;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::inclusive(10, 15);
+        assert_eq!(a, Interval::new(10, 16));
+        assert_eq!((a.len(), a.last()), (6, Some(15)));
+        assert_eq!(a.to_string(), "[10,16)");
+        assert!(a.contains(15) && !a.contains(16));
+        assert!(Interval::new(3, 3).is_empty());
+        assert_eq!(Interval::new(5, 3).len(), 0);
+        assert_eq!(a.shift(-10), Interval::new(0, 6));
+        assert!(a.covers(&Interval::inclusive(11, 15)));
+        assert!(!a.covers(&Interval::inclusive(11, 16)));
+
+        let tests = [
+            (
+                (Interval::inclusive(10, 15), Interval::inclusive(11, 20)),
+                Some(Interval::inclusive(11, 15)),
+                vec![Interval::inclusive(10, 10)],
+            ),
+            (
+                (Interval::inclusive(12, 15), Interval::inclusive(11, 20)),
+                Some(Interval::inclusive(12, 15)),
+                vec![],
+            ),
+            (
+                (Interval::inclusive(12, 15), Interval::inclusive(12, 20)),
+                Some(Interval::inclusive(12, 15)),
+                vec![],
+            ),
+            (
+                (Interval::inclusive(12, 15), Interval::inclusive(11, 15)),
+                Some(Interval::inclusive(12, 15)),
+                vec![],
+            ),
+            (
+                (Interval::inclusive(1, 9), Interval::inclusive(4, 5)),
+                Some(Interval::inclusive(4, 5)),
+                vec![Interval::inclusive(1, 3), Interval::inclusive(6, 9)],
+            ),
+            (
+                (Interval::inclusive(1, 3), Interval::inclusive(4, 5)),
+                None,
+                vec![Interval::inclusive(1, 3)],
+            ),
+        ];
+        for ((a, b), overlap, rest) in tests {
+            assert_eq!(a.intersect(&b), overlap, "{} and {}", a, b);
+            assert_eq!(a.overlaps(&b), overlap.is_some());
+            assert_eq!(a.difference(&b), rest, "{} without {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_inclusive() {
+        let a = Inclusive::new(10, 15);
+        assert_eq!(Interval::from(a), Interval::new(10, 16));
+        assert_eq!(Interval::new(10, 16).to_inclusive(), Some(a));
+        assert_eq!(Interval::new(3, 3).to_inclusive(), None);
+        assert_eq!(a.len(), 6);
+        assert_eq!(a.to_string(), "[10,15]");
+        assert!(a.contains(10) && a.contains(15) && !a.contains(16));
+        assert!(Inclusive::new(3, 2).is_empty() && !Inclusive::new(3, 3).is_empty());
+        assert_eq!(Inclusive::new(5, 3).len(), 0);
+        assert_eq!(Interval::from(Inclusive::new(5, 3)), Interval::new(5, 5));
+        assert!(a.covers(&Inclusive::new(11, 15)));
+        assert!(!a.covers(&Inclusive::new(11, 16)));
+
+        // Both ends are in, so the biggest value still fits.
+        let top = Inclusive::new(u8::MAX - 1, u8::MAX);
+        assert_eq!((top.len(), top.contains(u8::MAX)), (2, true));
+
+        let tests = [
+            (Inclusive::new(2, 4), Inclusive::new(6, 8), None),
+            (
+                Inclusive::new(2, 6),
+                Inclusive::new(6, 8),
+                Some(Inclusive::new(6, 6)),
+            ),
+            (
+                Inclusive::new(6, 6),
+                Inclusive::new(4, 6),
+                Some(Inclusive::new(6, 6)),
+            ),
+            (
+                Inclusive::new(2, 8),
+                Inclusive::new(3, 7),
+                Some(Inclusive::new(3, 7)),
+            ),
+        ];
+        for (a, b, overlap) in tests {
+            assert_eq!(a.intersect(&b), overlap, "{} and {}", a, b);
+            assert_eq!(a.overlaps(&b), overlap.is_some());
+        }
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set = [
+            Interval::new(5, 8),
+            Interval::new(0, 2),
+            Interval::new(7, 10),
+            Interval::new(2, 3),
+            Interval::new(20, 20),
+        ]
+        .into_iter()
+        .collect::<IntervalSet<i32>>();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(set.len(), 8);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(9)));
+        assert!(set.contains(2) && !set.contains(3));
+
+        let other = IntervalSet::from_iter([Interval::new(-5, 1), Interval::new(6, 12)]);
+        let tests = [
+            (
+                set.union(&other),
+                vec![Interval::new(-5, 3), Interval::new(5, 12)],
+            ),
+            (
+                set.intersection(&other),
+                vec![Interval::new(0, 1), Interval::new(6, 10)],
+            ),
+            (
+                set.difference(&other),
+                vec![Interval::new(1, 3), Interval::new(5, 6)],
+            ),
+            (
+                other.difference(&set),
+                vec![Interval::new(-5, 0), Interval::new(10, 12)],
+            ),
+            (
+                set.shift(100),
+                vec![Interval::new(100, 103), Interval::new(105, 110)],
+            ),
+        ];
+        for (got, want) in tests {
+            assert_eq!(got.iter().copied().collect::<Vec<_>>(), want);
+        }
+
+        let mut set = IntervalSet::new();
+        assert!(set.is_empty());
+        set.insert(Interval::inclusive(3, 5));
+        set.insert(Interval::inclusive(6, 7));
+        assert_eq!(set, IntervalSet::from(Interval::new(3, 8)));
+    }
+
+    #[test]
+    fn test_piecewise() {
+        // The seed-to-soil and soil-to-fertilizer maps from 2023 day 5.
+        let mut soil = Piecewise::new();
+        soil.add(Interval::new(98, 100), -48);
+        soil.add(Interval::new(50, 98), 2);
+        let mut fertilizer = Piecewise::new();
+        fertilizer.add(Interval::new(15, 52), -15);
+        fertilizer.add(Interval::new(52, 54), -15);
+        fertilizer.add(Interval::new(0, 15), 39);
+
+        let tests = [(79, 81), (14, 14), (55, 57), (13, 13), (99, 51)];
+        for (seed, want) in tests {
+            assert_eq!(soil.apply(seed), want);
+        }
+
+        let seeds = IntervalSet::from_iter([Interval::new(79, 93), Interval::new(96, 100)]);
+        assert_eq!(
+            soil.apply_set(&seeds),
+            IntervalSet::from_iter([
+                Interval::new(50, 52),
+                Interval::new(81, 95),
+                Interval::new(98, 100),
+            ])
+        );
+
+        let both = soil.then(&fertilizer);
+        for seed in -5..120 {
+            assert_eq!(
+                both.apply(seed),
+                fertilizer.apply(soil.apply(seed)),
+                "seed {}",
+                seed
+            );
+        }
+        assert_eq!(
+            both.apply_set(&seeds),
+            fertilizer.apply_set(&soil.apply_set(&seeds))
+        );
+    }
+}
@@ -0,0 +1,170 @@
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    return a;
+}
+
+/// The least common multiple, which is never negative. It's 0 if either is.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    return (a / gcd(a, b) * b).abs();
+}
+
+/// The gcd of everything in `values`, or 0 if there's nothing.
+pub fn gcd_all(values: impl IntoIterator<Item = i64>) -> i64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// The lcm of everything in `values`, or 1 if there's nothing.
+pub fn lcm_all(values: impl IntoIterator<Item = i64>) -> i64 {
+    values.into_iter().fold(1, lcm)
+}
+
+/// Extended Euclid: `(g, x, y)` where `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        return (-old_r, -old_x, -old_y);
+    }
+    return (old_r, old_x, old_y);
+}
+
+/// `a` mod `m` in `0..m`, even when `a` is negative. `m` has to be positive.
+pub fn modulo(a: i64, m: i64) -> i64 {
+    a.rem_euclid(m)
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if there is one (only when `a`
+/// and `m` are coprime).
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    if g != 1 {
+        return None;
+    }
+    return Some(modulo(x, m));
+}
+
+/// `base` to the power of `exp`, mod `m`, by repeated squaring.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut res = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    return res as i64;
+}
+
+/// Chinese Remainder Theorem: for `(residue, modulus)` pairs, the `(x, lcm)`
+/// where `x` is the smallest non-negative number with `x = residue (mod
+/// modulus)` for every pair, and every other answer is `x` plus a multiple of
+/// `lcm`. The moduli don't need to be coprime, but then there might not be any
+/// answer, in which case it's `None`. It's also `None` if `lcm` doesn't fit in
+/// an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0_i128, 1_i128);
+    for (residue, modulus) in congruences {
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        // Looking for x + m * k = residue (mod modulus), which can only be
+        // solved if the gcd divides the difference.
+        let (g, p, _) = extended_gcd(i64::try_from(m).ok()?, modulus as i64);
+        let (g, p) = (g as i128, p as i128);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * p).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+    return Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        let tests = [
+            ((12, 18), (6, 36)),
+            ((7, 5), (1, 35)),
+            ((-4, 6), (2, 12)),
+            ((0, 9), (9, 0)),
+        ];
+        for ((a, b), (want_gcd, want_lcm)) in tests {
+            assert_eq!(gcd(a, b), want_gcd, "gcd({}, {})", a, b);
+            assert_eq!(lcm(a, b), want_lcm, "lcm({}, {})", a, b);
+        }
+
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(vec![]), 1);
+    }
+
+    #[test]
+    fn test_modular() {
+        for (a, b) in [(240, 46), (46, 240), (-7, 3), (5, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g, "extended_gcd({}, {})", a, b);
+        }
+
+        assert_eq!(modulo(-3, 5), 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 7), 6);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        // Fermat: a^(p-1) = 1 mod p.
+        assert_eq!(mod_pow(123456789, 1000000006, 1000000007), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        let tests = [
+            (vec![(2, 3), (3, 5), (2, 7)], Some((23, 105))),
+            (vec![(0, 7), (12, 13)], Some((77, 91))),
+            // Not coprime, but they agree.
+            (vec![(3, 4), (1, 6)], Some((7, 12))),
+            (vec![(0, 4), (1, 6)], None),
+            (vec![(-1, 5)], Some((4, 5))),
+            (vec![], Some((0, 1))),
+        ];
+        for (congruences, want) in tests {
+            assert_eq!(crt(congruences.clone()), want, "{:?}", congruences);
+        }
+
+        // The 2020 day 13 example, with big enough moduli to need the i128s.
+        let buses = [(0, 1789), (1, 37), (2, 47), (3, 1889)];
+        let got = crt(buses.iter().map(|&(i, id)| (-i, id)));
+        assert_eq!(got, Some((1202161486, 1789 * 37 * 47 * 1889)));
+
+        // The lcm is too big for an i64, whether or not there are more after.
+        let big = [(1, 1 << 40), (2, 3_i64.pow(26))];
+        assert_eq!(crt(big), None);
+        assert_eq!(crt(big.into_iter().chain([(0, 5)])), None);
+    }
+}
@@ -0,0 +1,130 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache for recursive solutions, keyed on anything hashable: a tuple of
+/// indices, a `Vec` that can be looked up by slice, and so on.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Looks `key` up, counting it as a hit or a miss.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.cache.get(key) {
+            Some(value) => {
+                self.hits += 1;
+                Some(value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// The cached value for `key`, or else what `compute` gives, which is
+    /// cached for next time. `compute` gets the memo back so it can recurse.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        V: Clone,
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value.clone();
+        }
+
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        return value;
+    }
+
+    /// How many lookups found something.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// How many lookups came up empty.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets everything, including the hits and misses, e.g. before moving
+    /// on to a line whose keys mean something else.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn test_get_or_compute() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        // Each of 3..=90 finds n - 2 already worked out by n - 1.
+        assert_eq!((memo.hits(), memo.misses()), (87, 89));
+
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!((memo.hits(), memo.misses()), (88, 89));
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!((memo.hits(), memo.misses()), (0, 0));
+    }
+
+    #[test]
+    fn test_slice_keys() {
+        let mut memo: Memo<Vec<char>, usize> = Memo::new();
+        memo.insert(vec!['#', '?'], 2);
+        let line = ['.', '#', '?'];
+        assert_eq!(memo.get(&line[1..]), Some(&2));
+        assert_eq!(memo.get(&line[..]), None);
+        assert_eq!((memo.hits(), memo.misses()), (1, 1));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::utils::grid::Grid;
+
+/// The letters in the 6 pixel tall font, e.g. 2022 day 10's CRT.
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The letters in the 10 pixel tall font, e.g. 2018 day 10's stars.
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// The text spelled out by the lit pixels, in either of the fonts puzzles
+/// draw letters with. Blank rows around the text and blank columns between
+/// letters don't matter. `None` if there's nothing lit or something isn't a
+/// letter the font has.
+pub fn read(grid: &Grid<bool>) -> Option<String> {
+    let lit_rows = (0..grid.height())
+        .filter(|&y| grid.row(y).iter().any(|&lit| lit))
+        .collect::<Vec<usize>>();
+    let (top, bottom) = (*lit_rows.first()?, *lit_rows.last()?);
+    let font: &[(char, &str)] = match bottom - top + 1 {
+        6 => &SMALL,
+        10 => &LARGE,
+        _ => return None,
+    };
+
+    let lit_columns = (0..grid.width())
+        .map(|x| grid.column(x).any(|&lit| lit))
+        .collect::<Vec<bool>>();
+    let mut text = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if !lit_columns[x] {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < grid.width() && lit_columns[x] {
+            x += 1;
+        }
+        let glyph = (top..=bottom)
+            .map(|y| {
+                grid.row(y)[start..x]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        let (letter, _) = font.iter().find(|(_, pixels)| *pixels == glyph)?;
+        text.push(*letter);
+    }
+    return Some(text);
+}
+
+/// The same as `read`, for pixels drawn as lines of `#` (lit) and anything
+/// else (dark). Lines can be different lengths.
+pub fn read_lines(lines: &[String]) -> Option<String> {
+    let width = lines.iter().map(|line| line.chars().count()).max()?;
+    let mut cells = Vec::with_capacity(width * lines.len());
+    for line in lines {
+        let row = line.chars().map(|c| c == '#').collect::<Vec<bool>>();
+        cells.extend(
+            row.iter()
+                .copied()
+                .chain(std::iter::repeat(false))
+                .take(width),
+        );
+    }
+    return read(&Grid::from_vec(width, lines.len(), cells));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(s: &str) -> Vec<String> {
+        s.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_read_small() {
+        let screen = lines(
+            "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.",
+        );
+        assert_eq!(read_lines(&screen), Some(String::from("HELLO")));
+
+        // Every letter, with a blank column after each and some blank rows
+        // around them.
+        let mut screen = vec![String::new(); 8];
+        for (_, pixels) in SMALL {
+            for (y, row) in pixels.lines().enumerate() {
+                screen[y + 1].push_str(row);
+                screen[y + 1].push('.');
+            }
+        }
+        let want = SMALL.iter().map(|(c, _)| *c).collect::<String>();
+        assert_eq!(read_lines(&screen), Some(want));
+    }
+
+    #[test]
+    fn test_read_large() {
+        let mut screen = vec![String::new(); 10];
+        for letter in ['Z', 'A', 'X', 'N'] {
+            let (_, pixels) = LARGE.iter().find(|(c, _)| *c == letter).unwrap();
+            for (y, row) in pixels.lines().enumerate() {
+                screen[y].push_str(row);
+                screen[y].push_str("..");
+            }
+        }
+        assert_eq!(read_lines(&screen), Some(String::from("ZAXN")));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        let tests = [
+            "",
+            "....\n....",
+            // Only 5 rows tall.
+            "#..#\n#..#\n####\n#..#\n#..#",
+            // Not a letter.
+            "####\n####\n####\n####\n####\n####",
+        ];
+        for s in tests {
+            assert_eq!(read_lines(&lines(s)), None, "{:?}", s);
+        }
+
+        let grid = Grid::new(3, 6, false);
+        assert_eq!(read(&grid), None);
+    }
+}
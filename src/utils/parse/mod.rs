@@ -0,0 +1,214 @@
+use crate::error::AocError;
+use crate::utils::input::parse_at;
+use std::fmt;
+use std::str::FromStr;
+
+/// Every integer in `s`, in order, e.g. `[-2, 15]` from "x=-2, y=15". A `-`
+/// right after a letter or digit (as in "2-4") is taken as a dash rather
+/// than a sign.
+///
+/// A number that doesn't fit in a `T` (or a negative one when `T` is unsigned)
+/// is an `Err(AocError::Parse)` on line `line`, rather than being skipped or
+/// clamped, since either of those would quietly give a wrong answer.
+pub fn ints<T>(line: usize, s: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let bytes = s.as_bytes();
+    let mut res = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        res.push(parse_at(line, &s[start..i])?);
+    }
+    return Ok(res);
+}
+
+/// The runs of lines between blank lines. Extra blank lines don't make empty
+/// sections.
+pub fn sections(lines: &[String]) -> Vec<&[String]> {
+    numbered_sections(lines)
+        .into_iter()
+        .map(|(_, section)| section)
+        .collect()
+}
+
+/// The same as `sections`, along with the line number (counting from 1) each
+/// section starts on, for errors.
+pub fn numbered_sections(lines: &[String]) -> Vec<(usize, &[String])> {
+    let mut res = Vec::new();
+    let mut start = 0;
+    for section in lines.split(|line| line.is_empty()) {
+        if !section.is_empty() {
+            res.push((start + 1, section));
+        }
+        start += section.len() + 1;
+    }
+    return res;
+}
+
+/// The pieces of `line` that line up with the `{}`s in `pattern`, or `None` if
+/// the rest of `line` doesn't match `pattern` exactly. Each `{}` takes
+/// everything up to the first place the text after it in `pattern` shows up,
+/// so there has to be something between them.
+///
+/// `scan!` is usually nicer, since it parses the pieces too.
+pub fn scan_parts<'a>(pattern: &str, line: &'a str) -> Option<Vec<&'a str>> {
+    let mut literals = pattern.split("{}");
+    let mut rest = line.strip_prefix(literals.next().unwrap())?;
+    let mut parts = Vec::new();
+    let mut literals = literals.peekable();
+    while let Some(literal) = literals.next() {
+        if literals.peek().is_none() {
+            // The last `{}` runs up to whatever the line ends with.
+            parts.push(rest.strip_suffix(literal)?);
+            return Some(parts);
+        }
+
+        let end = rest.find(literal)?;
+        parts.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+
+    // No `{}`s at all.
+    if !rest.is_empty() {
+        return None;
+    }
+    return Some(parts);
+}
+
+/// Matches a line against a pattern with a `{}` for each value, parsing each
+/// into the type listed for it with `FromStr`:
+///
+/// ```ignore
+/// let (x, y, name): (i64, i64, String) =
+///     scan!(line_no, line, "{},{} is {}" => i64, i64, String)?;
+/// ```
+///
+/// Gives `Err(AocError::Parse)` on line `line_no` if the line doesn't match or a
+/// value doesn't parse.
+#[macro_export]
+macro_rules! scan {
+    ($line_no:expr, $line:expr, $pattern:literal => $($t:ty),+ $(,)?) => {
+        (|line_no: usize, line: &str| -> Result<($($t,)+), $crate::error::AocError> {
+            let parts = $crate::utils::parse::scan_parts($pattern, line).ok_or_else(|| {
+                $crate::error::AocError::parse(
+                    line_no,
+                    format!("{:?} doesn't look like {:?}", line, $pattern),
+                )
+            })?;
+            let mut parts = parts.into_iter();
+            Ok(($(
+                $crate::utils::input::parse_at::<$t>(
+                    line_no,
+                    parts.next().expect("more types than {}s in the pattern"),
+                )?,
+            )+))
+        })($line_no, &$line)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+
+    #[test]
+    fn test_ints() {
+        let tests = [
+            ("x=-2, y=15", vec![-2, 15]),
+            ("2-4,6-8", vec![2, 4, 6, 8]),
+            ("Valve AA has flow rate=0", vec![0]),
+            ("-5 - 3 --7", vec![-5, 3, -7]),
+            ("no numbers here", vec![]),
+        ];
+        for (s, want) in tests {
+            assert_eq!(ints::<i64>(1, s).unwrap(), want, "{:?}", s);
+        }
+        assert_eq!(ints::<usize>(1, "move 1 from 2 to 3").unwrap(), vec![1, 2, 3]);
+
+        for bad in ["x=300", "x=-2"] {
+            match ints::<u8>(5, bad) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, 5, "{:?}", bad),
+                got => panic!("{:?} should fail to parse, got {:?}", bad, got),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let lines = ["a", "b", "", "c", "", "", "d", ""]
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            sections(&lines),
+            vec![&lines[0..2], &lines[3..4], &lines[6..7]]
+        );
+        assert!(sections(&[]).is_empty());
+
+        let starts = numbered_sections(&lines)
+            .iter()
+            .map(|(line, _)| *line)
+            .collect::<Vec<usize>>();
+        assert_eq!(starts, vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn test_scan() {
+        let tests = [
+            (
+                "{}: {} {} {}",
+                "root: pppw + sjmn",
+                Some(vec!["root", "pppw", "+", "sjmn"]),
+            ),
+            ("{}: {} {} {}", "dbpl: 5", None),
+            ("x={}, y={}.", "x=1, y=-2.", Some(vec!["1", "-2"])),
+            ("x={}, y={}.", "x=1, y=-2", None),
+            ("[{}]", "[]", Some(vec![""])),
+            ("noop", "noop", Some(vec![])),
+            ("noop", "noop 1", None),
+        ];
+        for (pattern, line, want) in tests {
+            assert_eq!(
+                scan_parts(pattern, line),
+                want,
+                "{:?} with {:?}",
+                line,
+                pattern
+            );
+        }
+
+        let sensor = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let got = scan!(
+            3,
+            sensor,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}" => i64, i64, i64, i64
+        );
+        assert_eq!(got.unwrap(), (2, 18, -2, 15));
+
+        let line = String::from("a -> b");
+        let got = scan!(4, line, "{} -> {}" => String, char);
+        assert_eq!(got.unwrap(), (String::from("a"), 'b'));
+
+        for bad in ["a => b", "a -> bc"] {
+            match scan!(7, bad, "{} -> {}" => String, char) {
+                Err(AocError::Parse { line, .. }) => assert_eq!(line, 7, "{}", bad),
+                got => panic!("{:?} should fail, got {:?}", bad, got),
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
+/// `x` is the column and `y` is the row, so `y` grows going south (down the
+/// puzzle input), the same as `Grid`.
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
 pub struct Point<T> {
     pub x: T,
@@ -23,3 +26,524 @@ impl<T: Ord> PartialOrd for Point<T> {
         Some(self.cmp(other))
     }
 }
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Point {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+/// `|a - b|` without needing a signed type.
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// Taxicab distance: the steps between the two moving only orthogonally.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The steps between the two when diagonal moves are allowed too.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>> Point<T> {
+    /// The next point over in `dir`.
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.delta()
+    }
+
+    /// North, east, south and west, in that order.
+    pub fn neighbors4(self) -> [Self; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// Clockwise from north, diagonals included.
+    pub fn neighbors8(self) -> [Self; 8] {
+        let [n, e, s, w] = self.neighbors4();
+        [
+            n,
+            n.step(Direction::East),
+            e,
+            s.step(Direction::East),
+            s,
+            s.step(Direction::West),
+            w,
+            n.step(Direction::West),
+        ]
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// One step in this direction. North is up the input, so it's `-y`.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        };
+        Point {
+            x: T::from(x),
+            y: T::from(y),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_left().turn_left()
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Direction::North => 'N',
+            Direction::East => 'E',
+            Direction::South => 'S',
+            Direction::West => 'W',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// A point in space, e.g. a lava droplet's cube.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: std::fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Point3 {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>> Point3<T> {
+    /// The six points sharing a face: -x, +x, -y, +y, -z, +z.
+    pub fn neighbors6(self) -> [Self; 6] {
+        let step = |x: i8, y: i8, z: i8| self + Point3::new(T::from(x), T::from(y), T::from(z));
+        [
+            step(-1, 0, 0),
+            step(1, 0, 0),
+            step(0, -1, 0),
+            step(0, 1, 0),
+            step(0, 0, -1),
+            step(0, 0, 1),
+        ]
+    }
+
+    /// Every point touching this one, even at a corner or along an edge.
+    pub fn neighbors26(self) -> Vec<Self> {
+        PointN([self.x, self.y, self.z])
+            .neighbors()
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+}
+
+impl<T> From<PointN<T, 3>> for Point3<T> {
+    fn from(p: PointN<T, 3>) -> Self {
+        let [x, y, z] = p.0;
+        Self { x, y, z }
+    }
+}
+
+/// A point with any number of axes, for puzzles that go past three (e.g. a
+/// 4D Conway cube).
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct PointN<T, const N: usize>(pub [T; N]);
+
+impl<T: std::fmt::Display, const N: usize> fmt::Display for PointN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let axes = self
+            .0
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "({})", axes.join(","))
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add for PointN<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        PointN(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for PointN<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        PointN(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl<T: Copy + Mul<Output = T>, const N: usize> Mul<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        PointN(self.0.map(|a| a * scalar))
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        PointN(self.0.map(|a| -a))
+    }
+}
+
+impl<T: Copy + AddAssign, const N: usize> AddAssign for PointN<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: Copy + SubAssign, const N: usize> SubAssign for PointN<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>, const N: usize> PointN<T, N> {
+    /// Panics if `N` is zero.
+    pub fn manhattan(self, other: Self) -> T {
+        (0..N)
+            .map(|i| abs_diff(self.0[i], other.0[i]))
+            .reduce(|acc, d| acc + d)
+            .expect("a point needs at least one axis")
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>, const N: usize> PointN<T, N> {
+    /// The `2 * N` points one step along a single axis, down then up for each
+    /// axis in order.
+    pub fn face_neighbors(self) -> Vec<Self> {
+        let mut res = Vec::with_capacity(2 * N);
+        for axis in 0..N {
+            for d in [-1, 1] {
+                let mut p = self;
+                p.0[axis] = p.0[axis] + T::from(d);
+                res.push(p);
+            }
+        }
+        return res;
+    }
+
+    /// The `3^N - 1` points within one step along every axis.
+    pub fn neighbors(self) -> Vec<Self> {
+        let mut res = Vec::new();
+        for i in 0..3_usize.pow(N as u32) {
+            let mut rest = i;
+            let mut p = self;
+            let mut moved = false;
+            for axis in 0..N {
+                let d = (rest % 3) as i8 - 1;
+                rest /= 3;
+                if d != 0 {
+                    p.0[axis] = p.0[axis] + T::from(d);
+                    moved = true;
+                }
+            }
+            if moved {
+                res.push(p);
+            }
+        }
+        return res;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(1, 2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let tests = [
+            ((Point::new(0, 0), Point::new(3, -4)), (7, 4)),
+            ((Point::new(-2, 1), Point::new(-2, 1)), (0, 0)),
+            ((Point::new(8, 2), Point::new(2, 0)), (8, 6)),
+        ];
+        for ((a, b), (manhattan, chebyshev)) in tests {
+            assert_eq!(a.manhattan(b), manhattan);
+            assert_eq!(a.chebyshev(b), chebyshev);
+        }
+
+        let (a, b) = (Point::<usize>::new(1, 5), Point::<usize>::new(4, 2));
+        assert_eq!((a.manhattan(b), b.manhattan(a)), (6, 6));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point::<i32>::new(0, 0);
+        assert_eq!(
+            p.neighbors4(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        let n8 = p.neighbors8();
+        assert_eq!(n8[1], Point::new(1, -1));
+        assert_eq!(n8[7], Point::new(-1, -1));
+        assert!(n8.iter().all(|n| n.chebyshev(p) == 1));
+    }
+
+    #[test]
+    fn test_direction() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(
+                dir.delta::<i32>() + dir.opposite().delta(),
+                Point::new(0, 0)
+            );
+            assert_eq!(dir.turn_right().turn_right().turn_right().turn_right(), dir);
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(-1, 5, 0);
+        assert_eq!(a + b, Point3::new(0, 3, 3));
+        assert_eq!(a - b, Point3::new(2, -7, 3));
+        assert_eq!(a * 2, Point3::new(2, -4, 6));
+        assert_eq!(-a, Point3::new(-1, 2, -3));
+        assert_eq!(a.manhattan(b), 12);
+        assert_eq!(a.to_string(), "(1,-2,3)");
+
+        let n6 = a.neighbors6();
+        assert_eq!(n6[0], Point3::new(0, -2, 3));
+        assert_eq!(n6[5], Point3::new(1, -2, 4));
+        assert!(n6.iter().all(|n| n.manhattan(a) == 1));
+        assert_eq!(a.neighbors26().len(), 26);
+        assert!(!a.neighbors26().contains(&a));
+    }
+
+    #[test]
+    fn test_point_n() {
+        let a = PointN([1, 2, 3, 4]);
+        let mut b = PointN([0, -1, 1, 2]);
+        assert_eq!(a + b, PointN([1, 1, 4, 6]));
+        assert_eq!(a - b, PointN([1, 3, 2, 2]));
+        assert_eq!(-(a * 2), PointN([-2, -4, -6, -8]));
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(a.to_string(), "(1,2,3,4)");
+        b += a;
+        b -= PointN([1, 1, 1, 1]);
+        assert_eq!(b, PointN([0, 0, 3, 5]));
+
+        assert_eq!(PointN([0]).neighbors(), vec![PointN([-1]), PointN([1])]);
+        assert_eq!(PointN([0; 2]).neighbors().len(), 8);
+        assert_eq!(PointN([0; 3]).neighbors().len(), 26);
+        assert_eq!((a.face_neighbors().len(), a.neighbors().len()), (8, 80));
+        assert!(a.face_neighbors().iter().all(|n| n.manhattan(a) == 1));
+        assert!(a.neighbors().iter().all(|n| *n != a));
+    }
+}
@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the cost to reach every state it got to, and the
+/// state each one was reached from.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    pub dist: HashMap<S, C>,
+    pub prev: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Self {
+            dist: HashMap::new(),
+            prev: HashMap::new(),
+        }
+    }
+
+    /// `None` if the search never reached `state`.
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    /// The states from a start up to and including `state`.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        if !self.dist.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        return Some(path);
+    }
+}
+
+/// Visits every state reachable from `starts`, counting each step as 1.
+pub fn bfs<S, I, F>(starts: impl IntoIterator<Item = S>, mut neighbors: F) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.dist.contains_key(&start) {
+            search.dist.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(curr) = queue.pop_front() {
+        let d = search.dist[&curr];
+        for next in neighbors(&curr) {
+            if search.dist.contains_key(&next) {
+                continue;
+            }
+            search.dist.insert(next.clone(), d + 1);
+            search.prev.insert(next.clone(), curr.clone());
+            queue.push_back(next);
+        }
+    }
+    return search;
+}
+
+/// Visits every state reachable from `starts` in order of cost, where
+/// `neighbors` gives each next state with the cost of the step to it.
+pub fn dijkstra<S, C, I, F>(starts: impl IntoIterator<Item = S>, neighbors: F) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+{
+    let (_, search) = best_first(starts, neighbors, |_| C::default(), |_| false);
+    return search;
+}
+
+/// Searches from `start` until a state passes `is_goal`, trying the states
+/// `heuristic` guesses are closest first. The heuristic can't overestimate the
+/// remaining cost, or the goal might not be reached the cheapest way.
+///
+/// Gives back the goal that was found along with the search so far.
+pub fn astar<S, C, I, F, H, G>(
+    start: S,
+    neighbors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(S, Search<S, C>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let (goal, search) = best_first([start], neighbors, heuristic, is_goal);
+    return goal.map(|goal| (goal, search));
+}
+
+/// A state waiting in the heap. The heap pops its biggest item, so the
+/// ordering is reversed to pop the cheapest first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+fn best_first<S, C, I, F, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (Option<S>, Search<S, C>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        search.dist.insert(start.clone(), C::default());
+        heap.push(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            state: start,
+        });
+    }
+
+    while let Some(Queued { cost, state, .. }) = heap.pop() {
+        // A cheaper way here was already handled.
+        if cost > search.dist[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            return (Some(state), search);
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if let Some(&known) = search.dist.get(&next) {
+                if known <= next_cost {
+                    continue;
+                }
+            }
+            search.dist.insert(next.clone(), next_cost);
+            search.prev.insert(next.clone(), state.clone());
+            heap.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+    return (None, search);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::point::Point;
+
+    // A 5x5 room with a wall down the middle that's open at the bottom.
+    fn open(p: &Point<i32>) -> Vec<Point<i32>> {
+        p.neighbors4()
+            .into_iter()
+            .filter(|n| n.x >= 0 && n.x < 5 && n.y >= 0 && n.y < 5)
+            .filter(|n| n.x != 2 || n.y == 4)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let start = Point::new(0, 0);
+        let end = Point::new(4, 0);
+        let search = bfs([start], open);
+        assert_eq!(search.distance(&end), Some(12));
+        assert_eq!(search.distance(&Point::new(2, 0)), None);
+
+        let path = search.path(&end).unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let search = bfs([start, end], open);
+        assert_eq!(search.distance(&Point::new(2, 4)), Some(6));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going through "b" is more steps but cheaper.
+        let edges = HashMap::from([
+            ("a", vec![("b", 1), ("d", 10)]),
+            ("b", vec![("c", 2)]),
+            ("c", vec![("d", 3)]),
+            ("d", vec![]),
+        ]);
+        let search = dijkstra(["a"], |s| edges[s].clone());
+        assert_eq!(search.distance(&"d"), Some(6));
+        assert_eq!(search.path(&"d"), Some(vec!["a", "b", "c", "d"]));
+        assert_eq!(search.path(&"e"), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let start = Point::new(0, 0);
+        let end = Point::new(4, 0);
+        let neighbors = |p: &Point<i32>| open(p).into_iter().map(|n| (n, 1));
+
+        let (goal, search) = astar(start, neighbors, |p| p.manhattan(end), |p| *p == end).unwrap();
+        assert_eq!(goal, end);
+        assert_eq!(search.distance(&end), Some(12));
+        assert_eq!(search.path(&end).unwrap().len(), 13);
+
+        let walled_in = Point::new(9, 9);
+        assert!(astar(start, neighbors, |_| 0, |p| *p == walled_in).is_none());
+    }
+}
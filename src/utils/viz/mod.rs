@@ -0,0 +1,653 @@
+//! Drawing simulations, for `--visualize`. A day turns whatever it keeps its
+//! state in into a `Frame` with a char (and optionally a color) per cell and
+//! hands each one to a `Visualizer`, which shows it in the terminal or saves
+//! it as an image.
+
+use crate::error::AocError;
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+const DARK: Rgb = Rgb(15, 15, 35);
+const LIGHT: Rgb = Rgb(230, 230, 230);
+
+/// One cell of a frame. Cells without a color come out light in images,
+/// unless they're a space or a `.`.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Option<Rgb>,
+}
+
+impl Cell {
+    pub fn colored(ch: char, color: Rgb) -> Self {
+        Self {
+            ch,
+            color: Some(color),
+        }
+    }
+
+    fn rgb(&self) -> Rgb {
+        match (self.color, self.ch) {
+            (Some(color), _) => color,
+            (None, ' ' | '.') => DARK,
+            (None, _) => LIGHT,
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Self { ch, color: None }
+    }
+}
+
+impl From<Rgb> for Cell {
+    fn from(color: Rgb) -> Self {
+        Self::colored('█', color)
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ch)
+    }
+}
+
+/// A picture of the simulation at one moment. Its `Display` is just the
+/// chars, one line per row.
+pub type Frame = Grid<Cell>;
+
+fn to_i64<T: TryInto<i64>>(n: T) -> i64 {
+    n.try_into().ok().expect("coordinate doesn't fit in an i64")
+}
+
+/// The top left and bottom right corners of the box around `points`, or
+/// `None` if there aren't any.
+pub fn bounds<T, I>(points: I) -> Option<(Point<i64>, Point<i64>)>
+where
+    T: TryInto<i64>,
+    I: IntoIterator<Item = Point<T>>,
+{
+    let mut points = points
+        .into_iter()
+        .map(|p| Point::new(to_i64(p.x), to_i64(p.y)));
+    let first = points.next()?;
+    return Some(points.fold((first, first), |(min, max), p| {
+        (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    }));
+}
+
+/// A frame covering `min` to `max` (inclusive), drawing each point with `f`.
+pub fn draw<C, F>(min: Point<i64>, max: Point<i64>, f: F) -> Frame
+where
+    C: Into<Cell>,
+    F: Fn(Point<i64>) -> C,
+{
+    let width = (max.x - min.x + 1).max(0) as usize;
+    let height = (max.y - min.y + 1).max(0) as usize;
+    let mut cells = Vec::with_capacity(width * height);
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            cells.push(f(Point::new(min.x + x, min.y + y)).into());
+        }
+    }
+    return Grid::from_vec(width, height, cells);
+}
+
+/// A frame just big enough for every point in `points`, drawing each with `f`
+/// and the gaps between them with `empty`.
+pub fn from_points<T, V, C, F>(points: &HashMap<Point<T>, V>, empty: C, f: F) -> Frame
+where
+    T: Copy + Eq + std::hash::Hash + TryFrom<i64> + TryInto<i64>,
+    C: Into<Cell> + Copy,
+    F: Fn(&V) -> C,
+{
+    let Some((min, max)) = bounds(points.keys().copied()) else {
+        return Grid::from_vec(0, 0, Vec::new());
+    };
+    return draw(min, max, |p| {
+        let key = Point::new(
+            T::try_from(p.x).ok().unwrap(),
+            T::try_from(p.y).ok().unwrap(),
+        );
+        match points.get(&key) {
+            Some(v) => f(v).into(),
+            None => empty.into(),
+        }
+    });
+}
+
+/// A frame with a row per `Vec`, drawing each value with `f`. Short rows are
+/// padded with spaces.
+pub fn from_rows<V, C, F>(rows: &[Vec<V>], f: F) -> Frame
+where
+    C: Into<Cell>,
+    F: Fn(&V) -> C,
+{
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut cells = Vec::with_capacity(width * rows.len());
+    for row in rows {
+        cells.extend(row.iter().map(|v| f(v).into()));
+        cells.extend((row.len()..width).map(|_| Cell::from(' ')));
+    }
+    return Grid::from_vec(width, rows.len(), cells);
+}
+
+/// The frame with its colors as ANSI escapes, for the terminal.
+pub fn ansi(frame: &Frame) -> String {
+    let mut res = String::new();
+    for row in frame.rows() {
+        let mut current = None;
+        for cell in row {
+            if cell.color != current {
+                match cell.color {
+                    Some(Rgb(r, g, b)) => res.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b)),
+                    None => res.push_str("\x1b[0m"),
+                }
+                current = cell.color;
+            }
+            res.push(cell.ch);
+        }
+        if current.is_some() {
+            res.push_str("\x1b[0m");
+        }
+        res.push('\n');
+    }
+    return res;
+}
+
+/// Each pixel of the frame blown up to `scale` by `scale`, row by row.
+fn pixels(frame: &Frame, scale: usize) -> impl Iterator<Item = Rgb> + '_ {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    (0..height).flat_map(move |y| (0..width).map(move |x| frame.row(y / scale)[x / scale].rgb()))
+}
+
+/// The frame as a binary PPM image, with each cell `scale` pixels square.
+pub fn ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let mut res = format!(
+        "P6\n{} {}\n255\n",
+        frame.width() * scale,
+        frame.height() * scale
+    )
+    .into_bytes();
+    for Rgb(r, g, b) in pixels(frame, scale) {
+        res.extend([r, g, b]);
+    }
+    return res;
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    return !crc;
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    return (b << 16) | a;
+}
+
+/// The frame as a PNG image, with each cell `scale` pixels square. The data
+/// isn't compressed, which keeps this short and is fine for puzzle-sized
+/// frames.
+pub fn png(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut raw = Vec::with_capacity((width * 3 + 1) * height);
+    let pixels = pixels(frame, scale).collect::<Vec<Rgb>>();
+    for row in pixels.chunks(width.max(1)) {
+        // No filter on this row.
+        raw.push(0);
+        for &Rgb(r, g, b) in row {
+            raw.extend([r, g, b]);
+        }
+    }
+
+    // A zlib stream of "stored" deflate blocks, which can't be longer than
+    // 65535 bytes each.
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = raw.chunks(65535).collect::<Vec<&[u8]>>();
+    let blocks = if blocks.is_empty() {
+        vec![&[][..]]
+    } else {
+        blocks
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push((i + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(*block);
+    }
+    zlib.extend(adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bit RGB, no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut res = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+    for (kind, data) in [(b"IHDR", header), (b"IDAT", zlib), (b"IEND", Vec::new())] {
+        res.extend((data.len() as u32).to_be_bytes());
+        let start = res.len();
+        res.extend(kind);
+        res.extend(data);
+        let crc = crc32(&res[start..]);
+        res.extend(crc.to_be_bytes());
+    }
+    return res;
+}
+
+/// GIF's variable-width LZW, starting from codes one bit wider than
+/// `min_size`.
+fn lzw(min_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_size;
+    let end = clear + 1;
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_size + 1;
+    let mut next = end + 1;
+
+    let mut res = Vec::new();
+    let (mut bits, mut pending) = (0u32, 0u8);
+    let mut write = |code: u16, size: u8| {
+        bits |= (code as u32) << pending;
+        pending += size;
+        while pending >= 8 {
+            res.push(bits as u8);
+            bits >>= 8;
+            pending -= 8;
+        }
+    };
+
+    write(clear, code_size);
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let Some(p) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+        if let Some(&code) = dictionary.get(&(p, index)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        write(p, code_size);
+        if next < 4096 {
+            if next == 1 << code_size {
+                code_size += 1;
+            }
+            dictionary.insert((p, index), next);
+            next += 1;
+        } else {
+            write(clear, code_size);
+            dictionary.clear();
+            code_size = min_size + 1;
+            next = end + 1;
+        }
+        prefix = Some(index as u16);
+    }
+    if let Some(p) = prefix {
+        write(p, code_size);
+        // Decoders add an entry for this code too, before they see the end.
+        if next == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    write(end, code_size);
+    // Pad out the last byte.
+    write(0, 7);
+    return res;
+}
+
+/// The frames as an animated GIF that loops forever, showing each for
+/// `delay` and with each cell `scale` pixels square. Frames can be different
+/// sizes, and are drawn from the top left.
+///
+/// Panics if the frames have more than 256 colors between them.
+pub fn gif(frames: &[Frame], scale: usize, delay: Duration) -> Vec<u8> {
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut lookup: HashMap<Rgb, u8> = HashMap::new();
+    let mut images = Vec::new();
+    for frame in frames {
+        let indices = pixels(frame, scale)
+            .map(|color| {
+                *lookup.entry(color).or_insert_with(|| {
+                    palette.push(color);
+                    (palette.len() - 1)
+                        .try_into()
+                        .expect("GIFs can only have 256 colors")
+                })
+            })
+            .collect::<Vec<u8>>();
+        images.push((frame.width() * scale, frame.height() * scale, indices));
+    }
+
+    // The palette has to have a power of two colors, at least 2.
+    let mut bits = 1;
+    while 1 << bits < palette.len() {
+        bits += 1;
+    }
+    palette.resize(1 << bits, DARK);
+
+    let width = images.iter().map(|(w, _, _)| *w).max().unwrap_or(0) as u16;
+    let height = images.iter().map(|(_, h, _)| *h).max().unwrap_or(0) as u16;
+    let mut res = b"GIF89a".to_vec();
+    res.extend(width.to_le_bytes());
+    res.extend(height.to_le_bytes());
+    res.extend([0xF0 | (bits - 1), 0, 0]);
+    for Rgb(r, g, b) in palette {
+        res.extend([r, g, b]);
+    }
+    // Loop forever.
+    res.extend(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    let centiseconds = (delay.as_millis() / 10) as u16;
+    for (w, h, indices) in images {
+        // Clear each frame away before the next, in case it's smaller.
+        res.extend([0x21, 0xF9, 0x04, 0x08]);
+        res.extend(centiseconds.to_le_bytes());
+        res.extend([0, 0]);
+
+        res.extend([0x2C, 0, 0, 0, 0]);
+        res.extend((w as u16).to_le_bytes());
+        res.extend((h as u16).to_le_bytes());
+        res.push(0);
+
+        let min_size = bits.max(2);
+        res.push(min_size);
+        for block in lzw(min_size, &indices).chunks(255) {
+            res.push(block.len() as u8);
+            res.extend(block);
+        }
+        res.push(0);
+    }
+    res.push(0x3B);
+    return res;
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, clap::ValueEnum)]
+pub enum ImageFormat {
+    Png,
+    Ppm,
+}
+
+/// Where a `Visualizer` sends its frames.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Target {
+    /// Redrawn in place, one after another.
+    Terminal,
+    /// A numbered image file per frame in this directory.
+    Images(PathBuf, ImageFormat),
+    /// One animated GIF, written once every frame is in.
+    Gif(PathBuf),
+}
+
+impl Target {
+    /// `-` for the terminal, a path ending in `.gif` for an animation, or
+    /// else a directory to put each frame in as a `format` image.
+    pub fn parse(out: &str, format: ImageFormat) -> Self {
+        if out == "-" {
+            return Target::Terminal;
+        }
+        if out.ends_with(".gif") {
+            return Target::Gif(PathBuf::from(out));
+        }
+        return Target::Images(PathBuf::from(out), format);
+    }
+}
+
+/// Takes a day's frames and shows or saves them. Writing a frame can fail,
+/// but days shouldn't have to care: the first error stops any more frames
+/// being written and comes out of `finish`.
+pub struct Visualizer {
+    target: Target,
+    delay: Duration,
+    scale: usize,
+    frames: usize,
+    gif: Vec<Frame>,
+    error: Option<AocError>,
+}
+
+impl Visualizer {
+    pub fn new(target: Target) -> Self {
+        Self {
+            target,
+            delay: Duration::from_millis(100),
+            scale: 4,
+            frames: 0,
+            gif: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// How long each frame stays up in the terminal or a GIF.
+    pub fn with_delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// How many pixels square each cell is in images.
+    pub fn with_scale(self, scale: usize) -> Self {
+        Self { scale, ..self }
+    }
+
+    pub fn frame(&mut self, frame: &Frame) {
+        self.frames += 1;
+        if self.error.is_some() {
+            return;
+        }
+
+        match &self.target {
+            Target::Terminal => {
+                let mut stdout = io::stdout().lock();
+                let res =
+                    write!(stdout, "\x1b[H\x1b[2J{}", ansi(frame)).and_then(|_| stdout.flush());
+                if let Err(source) = res {
+                    self.error = Some(AocError::Io {
+                        path: String::from("stdout"),
+                        source,
+                    });
+                }
+                thread::sleep(self.delay);
+            }
+            Target::Images(dir, format) => {
+                let (bytes, extension) = match format {
+                    ImageFormat::Png => (png(frame, self.scale), "png"),
+                    ImageFormat::Ppm => (ppm(frame, self.scale), "ppm"),
+                };
+                let path = dir.join(format!("frame{:05}.{}", self.frames, extension));
+                let res = fs::create_dir_all(dir).and_then(|_| fs::write(&path, bytes));
+                if let Err(source) = res {
+                    self.error = Some(AocError::Io {
+                        path: path.display().to_string(),
+                        source,
+                    });
+                }
+            }
+            Target::Gif(_) => self.gif.push(frame.clone()),
+        }
+    }
+
+    /// How many frames the day has drawn so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Writes out the GIF, if that's the target, and says whether every frame
+    /// made it.
+    pub fn finish(self) -> Result<(), AocError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if let Target::Gif(path) = &self.target {
+            fs::write(path, gif(&self.gif, self.scale, self.delay)).map_err(|source| {
+                AocError::Io {
+                    path: path.display().to_string(),
+                    source,
+                }
+            })?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let points = HashMap::from([
+            (Point::new(-1, 2), 'a'),
+            (Point::new(1, 0), 'b'),
+            (Point::new(0, 1), 'c'),
+        ]);
+        let frame = from_points(&points, '.', |&c| c);
+        assert_eq!(frame.to_string(), "..b\n.c.\na..");
+
+        let frame = from_rows(&[vec![1, 2, 3], vec![4]], |&n| {
+            char::from_digit(n, 10).unwrap()
+        });
+        assert_eq!(frame.to_string(), "123\n4  ");
+
+        let frame = draw(Point::new(5, 5), Point::new(6, 5), |p| {
+            Cell::colored('#', Rgb(p.x as u8, 0, 0))
+        });
+        assert_eq!(ansi(&frame), "\x1b[38;2;5;0;0m#\x1b[38;2;6;0;0m#\x1b[0m\n");
+
+        assert_eq!(bounds(Vec::<Point<i32>>::new()), None);
+    }
+
+    #[test]
+    fn test_images() {
+        let frame = from_rows(&[vec!['#', '.']], |&c| c);
+        let image = ppm(&frame, 2);
+        assert_eq!(&image[..11], b"P6\n4 2\n255\n");
+        assert_eq!(image.len(), 11 + 4 * 2 * 3);
+
+        let image = png(&frame, 2);
+        assert_eq!(&image[..8], b"\x89PNG\r\n\x1a\n");
+        // The IHDR chunk says it's 4x2.
+        assert_eq!(&image[16..24], &[0, 0, 0, 4, 0, 0, 0, 2]);
+        // An empty IEND chunk always has the same CRC.
+        assert_eq!(&image[image.len() - 8..], b"IEND\xae\x42\x60\x82");
+    }
+
+    /// Undoes `lzw`, the way a GIF decoder would.
+    fn unlzw(min_size: u8, bytes: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_size;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> {
+            (0..clear)
+                .map(|i| vec![i as u8])
+                .chain([vec![], vec![]])
+                .collect()
+        };
+        let mut table = reset();
+        let mut code_size = min_size + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut res = Vec::new();
+        let mut bit = 0;
+        loop {
+            let mut code = 0;
+            for i in 0..code_size as usize {
+                if bytes[(bit + i) / 8] >> ((bit + i) % 8) & 1 == 1 {
+                    code |= 1 << i;
+                }
+            }
+            bit += code_size as usize;
+
+            if code == clear {
+                table = reset();
+                code_size = min_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return res;
+            }
+            let entry = match (table.get(code), &prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("code {} before anything else", code),
+            };
+            if let Some(prev) = prev {
+                table.push([prev, vec![entry[0]]].concat());
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            res.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw() {
+        let tests = [
+            (2, vec![]),
+            (2, vec![0, 1, 0, 1, 0, 1, 0, 1, 2, 3, 3, 3, 3, 3, 3, 3, 0]),
+            // Enough to fill the dictionary and start again.
+            (
+                8,
+                (0..20000u32)
+                    .map(|i| (i * 7919 % 251) as u8)
+                    .collect::<Vec<u8>>(),
+            ),
+            (2, vec![1; 10000]),
+        ];
+        for (min_size, indices) in tests {
+            assert_eq!(
+                unlzw(min_size, &lzw(min_size, &indices)),
+                indices,
+                "{} indices",
+                indices.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = [
+            from_rows(&[vec!['#', '.']], |&c| c),
+            from_rows(&[vec![Rgb(255, 0, 0)]], |&c| c),
+        ];
+        let image = gif(&frames, 1, Duration::from_millis(200));
+        assert_eq!(&image[..6], b"GIF89a");
+        // 2x1, with a 4 color palette for the 3 colors.
+        assert_eq!(&image[6..11], &[2, 0, 1, 0, 0xF1]);
+        assert_eq!(image.last(), Some(&0x3B));
+        assert_eq!(
+            image
+                .windows(4)
+                .filter(|w| w == &[0x21, 0xF9, 0x04, 0x08])
+                .count(),
+            2
+        );
+    }
+}